@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::debug;
+
+use crate::jira_client::IssueType;
+
+const CACHE_FILE_NAME: &str = "issue_types_cache.json";
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    fetched_at: u64,
+    issue_types: Vec<IssueType>,
+}
+
+/// On-disk cache of project issue types, kept separate from the config so it can be deleted safely
+#[derive(Debug, Clone)]
+pub struct IssueTypeCache {
+    path: PathBuf,
+    ttl: Duration,
+    refresh: bool,
+}
+
+/// Cache file location next to the config file
+pub fn cache_path(config_path: &Path) -> PathBuf {
+    config_path.with_file_name(CACHE_FILE_NAME)
+}
+
+pub fn clear_cache(config_path: &Path) -> io::Result<()> {
+    match fs::remove_file(cache_path(config_path)) {
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+impl IssueTypeCache {
+    /// With `refresh` set cached values are ignored but still updated
+    pub fn new(config_path: &Path, ttl: Duration, refresh: bool) -> Self {
+        IssueTypeCache {
+            path: cache_path(config_path),
+            ttl,
+            refresh,
+        }
+    }
+
+    fn read(&self) -> HashMap<String, CacheEntry> {
+        fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, key: &str) -> Option<Vec<IssueType>> {
+        if self.refresh {
+            return None;
+        }
+        let entry = self.read().remove(key)?;
+        if now_secs().saturating_sub(entry.fetched_at) > self.ttl.as_secs() {
+            return None;
+        }
+        Some(entry.issue_types)
+    }
+
+    /// Stores fresh values, failures only cost a refetch next time
+    pub fn put(&self, key: &str, issue_types: &[IssueType]) {
+        let mut entries = self.read();
+        entries.insert(
+            key.to_string(),
+            CacheEntry {
+                fetched_at: now_secs(),
+                issue_types: issue_types.to_vec(),
+            },
+        );
+        let result = serde_json::to_string(&entries)
+            .map_err(io::Error::other)
+            .and_then(|content| fs::write(&self.path, content));
+        if let Err(err) = result {
+            debug!(error = %err, path = %self.path.display(), "failed to write issue type cache");
+        }
+    }
+}
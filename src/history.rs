@@ -0,0 +1,69 @@
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+const HISTORY_FILE_NAME: &str = "history.jsonl";
+
+/// One line of the append-only log of created issues
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HistoryEntry {
+    /// RFC 3339 timestamp in local time
+    pub created_at: String,
+    pub project: String,
+    pub key: String,
+    pub title: String,
+}
+
+impl HistoryEntry {
+    pub fn new(project: &str, key: &str, title: &str) -> Self {
+        HistoryEntry {
+            created_at: Local::now().to_rfc3339(),
+            project: project.to_string(),
+            key: key.to_string(),
+            title: title.to_string(),
+        }
+    }
+}
+
+/// History file location next to the config file
+pub fn history_path(config_path: &Path) -> PathBuf {
+    config_path.with_file_name(HISTORY_FILE_NAME)
+}
+
+pub fn append_history(config_path: &Path, entry: &HistoryEntry) -> io::Result<()> {
+    let path = history_path(config_path);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut line = serde_json::to_string(entry).map_err(io::Error::other)?;
+    line.push('\n');
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(line.as_bytes())
+}
+
+/// Entries in the order they were written, a missing file means no history yet
+pub fn read_history(config_path: &Path) -> io::Result<Vec<HistoryEntry>> {
+    let content = match fs::read_to_string(history_path(config_path)) {
+        Ok(content) => content,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    Ok(content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(entry) => Some(entry),
+            Err(err) => {
+                // A line cut short by a crash shouldn't hide the rest of the log
+                debug!(error = %err, "skipping malformed history line");
+                None
+            }
+        })
+        .collect())
+}
@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::debug;
+
+use crate::jira_client::CreatedIssue;
+
+const IDEMPOTENCY_FILE_NAME: &str = "idempotency_keys.json";
+/// How long a key keeps pointing at the issue created with it
+const IDEMPOTENCY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Serialize, Deserialize)]
+struct IdempotencyEntry {
+    created_at: u64,
+    issue_key: String,
+    url: String,
+    /// Missing in keys stored by older versions
+    #[serde(default)]
+    self_url: String,
+    summary: String,
+}
+
+/// Issues created with `create --idempotency-key`, so a re-run returns them instead of
+/// creating duplicates
+#[derive(Debug, Clone)]
+pub struct IdempotencyStore {
+    path: PathBuf,
+    profile_name: String,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+impl IdempotencyStore {
+    /// Keys are scoped to the profile, the same key may be reused against another instance
+    pub fn new(config_path: &Path, profile_name: &str) -> Self {
+        IdempotencyStore {
+            path: config_path.with_file_name(IDEMPOTENCY_FILE_NAME),
+            profile_name: profile_name.to_string(),
+        }
+    }
+
+    fn entry_key(&self, key: &str) -> String {
+        format!("{}:{}", self.profile_name, key)
+    }
+
+    fn read(&self) -> HashMap<String, IdempotencyEntry> {
+        fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, key: &str) -> Option<CreatedIssue> {
+        let entry = self.read().remove(&self.entry_key(key))?;
+        if now_secs().saturating_sub(entry.created_at) > IDEMPOTENCY_TTL.as_secs() {
+            return None;
+        }
+        Some(CreatedIssue {
+            key: entry.issue_key,
+            url: entry.url,
+            self_url: entry.self_url,
+            summary: entry.summary,
+        })
+    }
+
+    /// Remembers the issue and drops expired keys
+    pub fn put(&self, key: &str, issue: &CreatedIssue) -> io::Result<()> {
+        let now = now_secs();
+        let mut entries = self.read();
+        entries
+            .retain(|_, entry| now.saturating_sub(entry.created_at) <= IDEMPOTENCY_TTL.as_secs());
+        entries.insert(
+            self.entry_key(key),
+            IdempotencyEntry {
+                created_at: now,
+                issue_key: issue.key.clone(),
+                url: issue.url.clone(),
+                self_url: issue.self_url.clone(),
+                summary: issue.summary.clone(),
+            },
+        );
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string(&entries).map_err(io::Error::other)?;
+        debug!(path = %self.path.display(), key, "storing idempotency key");
+        fs::write(&self.path, content)
+    }
+}
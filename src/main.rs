@@ -1,20 +1,51 @@
 use core::panic;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, ExitCode};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
-use clap::{Parser, Subcommand};
-use inquire::{Confirm, Select, Text};
-use once_cell::sync::Lazy;
-use validator::{ValidateEmail, ValidateUrl};
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, Weekday};
+use clap::{Args, Parser, Subcommand};
+use inquire::{Confirm, InquireError, MultiSelect, Password, Select, Text, error::InquireResult};
+use tokio::net::TcpListener;
+use tracing::{Level, debug};
+use tracing_subscriber::{filter::Targets, layer::SubscriberExt, util::SubscriberInitExt};
+use validator::ValidateEmail;
 
+#[macro_use]
+mod output;
+
+mod cache;
 mod config;
+mod history;
+mod idempotency;
 mod jira_client;
+mod oauth;
+mod render;
 
-use config::Config;
-use jira_client::JiraClient;
+use cache::{IssueTypeCache, clear_cache};
+use config::{AuthScheme, Config, IssueTemplate, Profile, ProjectConfig};
+use futures_util::{StreamExt, stream};
+use history::{HistoryEntry, append_history, read_history};
+use idempotency::IdempotencyStore;
+use jira_client::{
+    Component, CreateField, CreatedIssue, IssueDetails, IssueType, JiraClient, JiraClientError,
+    MAX_SEARCH_PAGE, NewIssue, Priority, RequestErrorKind, SearchPage, Sprint, Version,
+    is_single_line, normalize_base_url,
+};
+use render::{OutputFormat, csv_row, print_issues};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use serde_json::{Value, json};
 
-use crate::{
-    config::{CONFIG_PATH, LoadConfigError, load_config, save_config},
-    jira_client::{create_issue, get_project_issue_types, test_connection},
+use crate::config::{
+    API_TOKEN_ENV, API_TOKEN_FILE_ENV, CONFIG_PATH, EMAIL_ENV, JIRA_URL_ENV, LoadConfigError,
+    config_template, expand_path, load_config, save_config,
 };
 
 #[derive(Parser)]
@@ -23,6 +54,22 @@ use crate::{
 #[command(long_about = "Create Jira issues quickly from the command line.
 Use 'fast-task create' for guided issue creation")]
 struct Cli {
+    /// Path to the config file
+    #[arg(long = "config", global = true, env = "FAST_TASK_CONFIG")]
+    config_path: Option<PathBuf>,
+    /// Profile to use instead of the default one
+    #[arg(long, global = true)]
+    profile: Option<String>,
+    /// Log HTTP requests, repeat for more detail (-vvv logs error bodies)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+    /// Plain output without emoji or colors, also enabled by the NO_COLOR variable
+    #[arg(long, global = true)]
+    no_color: bool,
+    /// Answer confirmation prompts such as "Create this issue?" with yes.
+    /// Destructive actions keep their own flags
+    #[arg(short, long, global = true)]
+    yes: bool,
     #[command(subcommand)]
     command: Commands,
 }
@@ -30,356 +77,5347 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Configure Jira connection settings
-    Config,
+    Config {
+        #[command(subcommand)]
+        command: Option<ConfigCommands>,
+    },
+    /// Log in to Jira Cloud with OAuth 2.0 instead of an api token
+    Login {
+        /// Client id of your OAuth 2.0 (3LO) app from developer.atlassian.com
+        #[arg(long, env = "FAST_TASK_OAUTH_CLIENT_ID")]
+        client_id: Option<String>,
+        /// Client secret of the app
+        #[arg(long, env = "FAST_TASK_OAUTH_CLIENT_SECRET", hide_env_values = true)]
+        client_secret: Option<String>,
+        /// Local port of the callback, the app must allow http://localhost:<PORT>/callback
+        #[arg(long, default_value_t = 8910)]
+        port: u16,
+    },
     /// Add a project to work with
     AddProject,
+    /// Remove a configured project
+    RemoveProject {
+        /// Project key to remove, prompted when omitted
+        #[arg(long)]
+        key: Option<String>,
+    },
     /// List configured projects
     ListProjects,
-    /// Test Jira connection
-    Test,
+    /// Test Jira connection, exits with a non-zero code when it fails
+    Test {
+        /// Print nothing, only the exit code tells the result
+        #[arg(short, long)]
+        quiet: bool,
+        /// Only check that the Jira server answers, no credentials are sent
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Show the user the configured credentials belong to
+    Whoami,
+    /// Check the config for problems without contacting Jira
+    Validate,
+    /// List issues created with fast-task, newest first
+    History {
+        /// Only show issues of this project
+        #[arg(long)]
+        project: Option<String>,
+        /// Maximum number of issues to show
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
     /// Create a new issue
-    Create,
+    Create(Box<CreateArgs>),
+    /// Create issues from a JSON lines file, one issue object per line
+    BulkCreate {
+        /// File with lines like {"title": "...", "type": "Bug", "labels": ["ui"]}, `-` reads stdin
+        file: PathBuf,
+        /// Project for lines without "project", may be omitted when a single project
+        /// is configured
+        #[arg(long)]
+        project: Option<String>,
+        /// Accept "project" values that aren't configured, Jira still has to know them
+        #[arg(long)]
+        allow_unknown_projects: bool,
+        /// Issue type for lines without "type", the project's default type otherwise
+        #[arg(long = "type")]
+        issue_type: Option<String>,
+        /// How many issues are sent to Jira at once
+        #[arg(long, default_value_t = DEFAULT_BULK_CONCURRENCY, value_parser = clap::value_parser!(u8).range(1..=20))]
+        concurrency: u8,
+        /// Don't add the project's default_labels to the issues
+        #[arg(long)]
+        no_default_labels: bool,
+    },
+    /// List issues of a project
+    ListIssues {
+        /// Project key, prompted when several projects are configured
+        #[arg(long)]
+        project: Option<String>,
+        /// Only show issues in this status
+        #[arg(long)]
+        status: Option<String>,
+        /// Maximum number of issues to show
+        #[arg(long, default_value_t = 20)]
+        limit: u32,
+        /// Fetch every matching issue instead of the first --limit
+        #[arg(long, conflicts_with = "limit")]
+        all: bool,
+        /// How to print the issues
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+    },
+    /// List issues assigned to you in the configured projects
+    MyIssues {
+        /// Only show issues updated within this period, e.g. 7d or 2w
+        #[arg(long, value_parser = parse_since)]
+        since: Option<String>,
+        /// Only show issues in this status
+        #[arg(long)]
+        status: Option<String>,
+        /// Maximum number of issues to show
+        #[arg(long, default_value_t = 20)]
+        limit: u32,
+        /// Fetch every matching issue instead of the first --limit
+        #[arg(long, conflicts_with = "limit")]
+        all: bool,
+        /// How to print the issues
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+    },
+    /// Run a JQL query and list the matching issues
+    Search {
+        /// JQL, e.g. "project = WEB AND status = 'In Progress'"
+        #[arg(value_parser = parse_jql)]
+        jql: String,
+        /// Maximum number of issues to show
+        #[arg(long, default_value_t = 50)]
+        limit: u32,
+        /// Fetch every matching issue instead of the first --limit
+        #[arg(long, conflicts_with = "limit")]
+        all: bool,
+        /// How to print the issues
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+        /// Comma-separated fields to show instead of status and assignee, e.g. priority,labels
+        #[arg(long, value_delimiter = ',')]
+        fields: Vec<String>,
+        /// Print the issues as returned by Jira
+        #[arg(long, conflicts_with = "output")]
+        json: bool,
+    },
+    /// Show a single issue
+    View {
+        /// Issue key, e.g. PROJ-123
+        key: String,
+        /// Print the raw Jira response with all fields
+        #[arg(long)]
+        json: bool,
+    },
+    /// Move an issue to another workflow status
+    Transition {
+        /// Issue key, e.g. PROJ-123
+        key: String,
+        /// Transition name, prompted when omitted
+        #[arg(long)]
+        to: Option<String>,
+    },
+    /// Delete an issue after confirming its summary
+    Delete {
+        /// Issue key, e.g. PROJ-123
+        key: String,
+        /// Also delete the sub-tasks, Jira refuses to delete an issue that has any otherwise
+        #[arg(long)]
+        delete_subtasks: bool,
+        /// Delete without asking, the global --yes doesn't apply to deleting
+        #[arg(long)]
+        force: bool,
+    },
+    /// Link two issues, e.g. `fast-task link ABC-1 ABC-2 --type Blocks`
+    Link {
+        /// Issue the link starts from, e.g. the blocking one
+        from: String,
+        /// Issue the link points to
+        to: String,
+        /// Link type name, prompted when omitted
+        #[arg(long = "type")]
+        link_type: Option<String>,
+    },
+    /// Comment on an issue
+    Comment {
+        /// Issue key, e.g. PROJ-123
+        key: String,
+        /// Comment text, prompted when omitted
+        #[arg(long)]
+        body: Option<String>,
+    },
+    /// Update fields of an existing issue
+    Update {
+        /// Issue key, e.g. PROJ-123
+        key: String,
+        /// New issue title
+        #[arg(long)]
+        summary: Option<String>,
+        /// New issue description
+        #[arg(long)]
+        description: Option<String>,
+        /// New assignee account id (Jira Cloud) or username (Data Center), `me` for yourself
+        #[arg(long)]
+        assignee: Option<String>,
+        /// New priority name or id
+        #[arg(long)]
+        priority: Option<String>,
+    },
+    /// Attach files to an issue
+    Attach {
+        /// Issue key, e.g. PROJ-123
+        key: String,
+        /// Files to upload
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// List agile boards, e.g. to find the board_id sprints are taken from
+    Boards {
+        /// Only boards showing this project's issues
+        #[arg(long)]
+        project: Option<String>,
+        /// Save this board as the project's board_id for sprint selection
+        #[arg(long, value_name = "BOARD_ID", requires = "project")]
+        assign: Option<u64>,
+    },
+    /// Manage configured projects in bulk
+    Projects {
+        #[command(subcommand)]
+        command: ProjectsCommands,
+    },
+    /// Manage the local issue type cache
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommands,
+    },
+    /// Manage Jira profiles
+    Profile {
+        #[command(subcommand)]
+        command: ProfileCommands,
+    },
+    /// Manage the api token of the profile
+    Token {
+        #[command(subcommand)]
+        command: TokenCommands,
+    },
+    /// Manage issue templates used by `create --template`
+    Template {
+        #[command(subcommand)]
+        command: TemplateCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Print the settings in effect, including environment overrides
+    Show {
+        /// Print the settings as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Open the config file in $VISUAL or $EDITOR and validate it afterwards
+    Edit,
+}
+
+#[derive(Subcommand)]
+enum ProjectsCommands {
+    /// Add every project you can access in Jira to the config
+    Sync {
+        /// Only import projects whose key or name contains this, case-insensitive
+        #[arg(long)]
+        filter: Option<String>,
+        /// Remove configured projects that aren't imported instead of keeping them
+        #[arg(long)]
+        replace: bool,
+    },
+    /// Change the display name of a configured project
+    Rename {
+        key: String,
+        /// New display name, prompted when omitted
+        #[arg(long)]
+        name: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheCommands {
+    /// Delete cached issue types
+    Clear,
+}
+
+#[derive(Subcommand)]
+enum TokenCommands {
+    /// Replace the api token, the new one is checked against Jira before it is saved
+    Rotate {
+        /// Swap the backup kept by the last rotation back in instead of entering a new token
+        #[arg(long)]
+        restore: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProfileCommands {
+    /// Add a new profile and configure its connection
+    Add { name: String },
+    /// List configured profiles
+    List,
+    /// Make a profile the default one
+    Switch { name: String },
+}
+
+#[derive(Subcommand)]
+enum TemplateCommands {
+    /// List issue templates
+    List,
+    /// Add a template or replace an existing one
+    Add {
+        name: String,
+        /// Project key
+        #[arg(long)]
+        project: Option<String>,
+        /// Issue type name or id
+        #[arg(long = "type")]
+        issue_type: Option<String>,
+        /// Priority name or id
+        #[arg(long)]
+        priority: Option<String>,
+        /// Label to add, can be repeated
+        #[arg(long = "label")]
+        labels: Vec<String>,
+        /// Component name or id, can be repeated
+        #[arg(long = "component")]
+        components: Vec<String>,
+        /// Text prepended to the issue title
+        #[arg(long)]
+        summary_prefix: Option<String>,
+    },
+    /// Remove a template
+    Remove { name: String },
+}
+
+#[derive(Args)]
+struct CreateArgs {
+    /// Template to pre-fill the issue from, see `fast-task template list`
+    #[arg(long)]
+    template: Option<String>,
+    /// Project key or display name to create the issue in
+    #[arg(long)]
+    project: Option<String>,
+    /// Issue title
+    #[arg(long)]
+    title: Option<String>,
+    /// Issue description
+    #[arg(long)]
+    description: Option<String>,
+    /// Read the description from a file, `-` reads stdin. Wins over --description
+    #[arg(long)]
+    description_file: Option<PathBuf>,
+    /// Issue type name or id
+    #[arg(long = "type")]
+    issue_type: Option<String>,
+    /// Assignee account id (Jira Cloud) or username (Data Center), `me` for yourself
+    #[arg(long)]
+    assignee: Option<String>,
+    /// Reporter account id (Jira Cloud) or username (Data Center), needs the
+    /// Modify Reporter permission
+    #[arg(long)]
+    reporter: Option<String>,
+    /// Priority name or id
+    #[arg(long)]
+    priority: Option<String>,
+    /// Watcher account id (Jira Cloud) or username (Data Center), can be repeated
+    #[arg(long = "watch", value_name = "USER")]
+    watchers: Vec<String>,
+    /// Label to add, can be repeated
+    #[arg(long = "label")]
+    labels: Vec<String>,
+    /// Don't add the project's default_labels to this issue
+    #[arg(long)]
+    no_default_labels: bool,
+    /// Leave the issue unassigned instead of using the project's default_assignee
+    #[arg(long, conflicts_with = "assignee")]
+    no_default_assignee: bool,
+    /// Component name or id, can be repeated
+    #[arg(long = "component")]
+    components: Vec<String>,
+    /// Fix version name or id, can be repeated
+    #[arg(long = "fix-version", value_name = "VERSION")]
+    fix_versions: Vec<String>,
+    /// Offer and accept released and archived versions for --fix-version too
+    #[arg(long)]
+    include_released: bool,
+    /// Parent issue key, required for sub-tasks, e.g. ABC-12
+    #[arg(long)]
+    parent: Option<String>,
+    /// Due date as YYYY-MM-DD, `today`, `tomorrow`, `+3d`, `+2w` or `next friday`
+    #[arg(long = "due", value_name = "DATE", value_parser = parse_due)]
+    due_date: Option<NaiveDate>,
+    /// Accept a due date in the past
+    #[arg(long)]
+    force: bool,
+    /// Sprint id, or a sprint name looked up on the project's board_id
+    #[arg(long)]
+    sprint: Option<String>,
+    /// Original estimate in Jira's duration syntax, e.g. `3h` or `1w 2d`
+    #[arg(long = "estimate", value_name = "DURATION", value_parser = parse_estimate)]
+    original_estimate: Option<String>,
+    /// Remaining estimate, Jira sets it to the original estimate when omitted
+    #[arg(long, value_name = "DURATION", value_parser = parse_estimate)]
+    remaining_estimate: Option<String>,
+    /// Where a bug occurred, e.g. browser and OS. Prompted when the issue type requires it
+    #[arg(long)]
+    environment: Option<String>,
+    /// Raw field as key=value, e.g. customfield_10011=Epic. Values that parse as JSON
+    /// are sent as JSON, anything else as a string. Can be repeated
+    #[arg(long = "field", value_name = "KEY=VALUE", value_parser = parse_field)]
+    fields: Vec<(String, Value)>,
+    /// Open the created issue in the browser
+    #[arg(long)]
+    open: bool,
+    /// Wait until Jira serves the created issue before going on, for scripts that
+    /// query it right away
+    #[arg(long)]
+    wait: bool,
+    /// Like --wait, then open the issue in the browser
+    #[arg(long)]
+    wait_and_open: bool,
+    /// Also print the issue's REST API url, `--json` always includes it as self_url
+    #[arg(long)]
+    raw_url: bool,
+    /// Move the created issue to the profile's start_status, "In Progress" by default
+    #[arg(long)]
+    start: bool,
+    /// Print the request that would be sent instead of creating the issue
+    #[arg(long)]
+    dry_run: bool,
+    /// Look for an open issue with the same title in the project first and ask
+    /// whether to create another one. Skipped under --yes
+    #[arg(long)]
+    dedupe: bool,
+    /// Like --dedupe, but also checks under --yes, where a duplicate aborts the create
+    #[arg(long)]
+    dedupe_strict: bool,
+    /// Key identifying this create, re-running with the same key within 24 hours
+    /// returns the issue created the first time instead of a duplicate
+    #[arg(long, value_name = "KEY")]
+    idempotency_key: Option<String>,
+    /// Fetch issue types from Jira even when they are cached
+    #[arg(long)]
+    refresh: bool,
+    /// Create in the project used last time without prompting for it
+    #[arg(long)]
+    last: bool,
+    /// Use the project's default issue type without prompting
+    #[arg(long)]
+    use_defaults: bool,
+    /// Print the created issue as JSON, `--open` is ignored
+    #[arg(long)]
+    json: bool,
+    /// Print only the created issue's key, e.g. for `KEY=$(fast-task create ... -q)`.
+    /// Errors and warnings go to stderr
+    #[arg(short, long, conflicts_with_all = ["json", "dry_run"])]
+    quiet: bool,
+    /// With --quiet, print the issue's url instead of its key
+    #[arg(long, requires = "quiet")]
+    url: bool,
+    /// Don't start the interactive description from the config's description_template
+    #[arg(long)]
+    no_template: bool,
+    /// Title prefix from the template
+    #[arg(skip)]
+    summary_prefix: Option<String>,
+    /// `description_template` from the config, unless `--no-template`
+    #[arg(skip)]
+    description_template: Option<String>,
+}
+
+impl CreateArgs {
+    /// Optional fields are only prompted for when the title was entered interactively,
+    /// scripted callers pass them as flags
+    fn prompts_optional_fields(&self) -> bool {
+        self.title.is_none()
+    }
+
+    /// Past due dates are most likely typos, `--force` lets them through
+    fn check_due_date(&self) -> Result<(), IssueCreateError> {
+        match self.due_date {
+            Some(due_date) if !self.force && due_date < Local::now().date_naive() => {
+                Err(IssueCreateError::PastDueDate(due_date))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Replaces the description with the contents of `--description-file`
+    fn read_description_file(&mut self) -> Result<(), IssueCreateError> {
+        let Some(ref path) = self.description_file else {
+            return Ok(());
+        };
+        let read_error =
+            |reason: String| IssueCreateError::DescriptionFile(path.display().to_string(), reason);
+
+        let mut content = Vec::new();
+        if path.as_os_str() == "-" {
+            io::stdin()
+                .read_to_end(&mut content)
+                .map_err(|err| read_error(err.to_string()))?;
+        } else {
+            content = fs::read(path).map_err(|err| read_error(err.to_string()))?;
+        }
+        let description =
+            String::from_utf8(content).map_err(|_| read_error("not valid UTF-8".to_string()))?;
+        self.description = Some(description);
+        Ok(())
+    }
+
+    /// Fills fields that weren't passed as flags from the template
+    fn with_template(mut self, template: &IssueTemplate) -> Self {
+        self.project = self.project.or_else(|| template.project.clone());
+        self.issue_type = self.issue_type.or_else(|| template.issue_type.clone());
+        self.priority = self.priority.or_else(|| template.priority.clone());
+        if self.labels.is_empty() {
+            self.labels = template.labels.clone();
+        }
+        if self.components.is_empty() {
+            self.components = template.components.clone();
+        }
+        if let Some(ref prefix) = template.summary_prefix {
+            self.title = self.title.map(|title| {
+                if title.starts_with(prefix.as_str()) {
+                    title
+                } else {
+                    format!("{}{}", prefix, title)
+                }
+            });
+            self.summary_prefix = Some(prefix.clone());
+        }
+        self
+    }
+
+    /// The one line `--quiet` prints
+    fn quiet_result<'a>(&self, issue: &'a CreatedIssue) -> &'a str {
+        if self.url { &issue.url } else { &issue.key }
+    }
+
+    /// `--assignee`, or the project's default_assignee unless `--no-default-assignee`
+    fn assignee(&self, profile: &Profile, project: &str) -> Option<String> {
+        self.assignee.clone().or_else(|| {
+            profile
+                .projects
+                .get(project)
+                .filter(|_| !self.no_default_assignee)
+                .and_then(|project| project.default_assignee.clone())
+        })
+    }
+
+    /// Issue type from `--type`, or the project default under `--use-defaults`
+    fn issue_type(&self, profile: &Profile) -> Option<String> {
+        self.issue_type.clone().or_else(|| {
+            self.project
+                .as_ref()
+                .filter(|_| self.use_defaults)
+                .and_then(|project| profile.projects.get(project))
+                .and_then(|project| project.default_issue_type.clone())
+        })
+    }
 }
 
 #[derive(Debug, Error)]
 enum IssueCreateError {
-    #[error("Project: {0}. Jira client error: {1}")]
+    #[error("Project: {0}. {1}")]
     JiraClient(String, String),
     #[error("Issue title cannot be empty")]
     EmptyTitle,
+    #[error("Issue title must be a single line")]
+    InvalidSummary,
     #[error("No issue types found for project '{0}'")]
     IssueTypesNotFound(String),
+    #[error("Project '{0}' is not configured")]
+    UnknownProject(String),
+    #[error("Issue type '{0}' not found for project '{1}'")]
+    UnknownIssueType(String, String),
+    #[error("Priority '{0}' not found")]
+    UnknownPriority(String),
+    #[error("Component '{0}' not found for project '{1}'")]
+    UnknownComponent(String, String),
+    #[error("Fix version '{0}' not found for project '{1}'")]
+    UnknownVersion(String, String),
+    #[error("Fix version '{0}' is released or archived, pass --include-released to use it anyway")]
+    ReleasedVersion(String),
+    #[error("Label '{0}' cannot contain spaces")]
+    InvalidLabel(String),
+    #[error("Cannot read description from '{0}': {1}")]
+    DescriptionFile(String, String),
     #[error("Failed to select an option")]
     SelectOption,
+    #[error("Issue type '{0}' is a sub-task and needs a parent, pass --parent")]
+    MissingParent(String),
+    #[error("Due date {0} is in the past, pass --force to use it anyway")]
+    PastDueDate(NaiveDate),
+    #[error("Sprint '{0}' not found among active and future sprints of project '{1}'")]
+    UnknownSprint(String, String),
+    #[error("Project '{0}' has no board_id configured, pass the sprint id instead of its name")]
+    MissingBoard(String),
+    #[error(
+        "Jira refused to set the reporter: {0}. Setting it needs the Modify Reporter permission and the field on the create screen"
+    )]
+    ReporterNotSettable(String),
+    #[error("{0} with the same title is still open: {1}")]
+    Duplicate(String, String),
     #[error("Operation canceled by user")]
     Canceled,
+    #[error("Cannot prompt: {0}")]
+    Prompt(String),
+}
+
+impl From<InquireError> for IssueCreateError {
+    fn from(err: InquireError) -> Self {
+        match err {
+            InquireError::OperationCanceled | InquireError::OperationInterrupted => {
+                IssueCreateError::Canceled
+            }
+            err => IssueCreateError::Prompt(err.to_string()),
+        }
+    }
+}
+
+/// Exit code of a process stopped with Ctrl-C
+const CANCELED_EXIT_CODE: u8 = 130;
+/// Rows shown by the project and issue type pickers, typing filters them fuzzily
+const PICKER_PAGE_SIZE: usize = 12;
+
+impl IssueCreateError {
+    /// Distinct per variant so wrapper scripts can tell failures apart
+    fn exit_code(&self) -> u8 {
+        match self {
+            IssueCreateError::JiraClient(..) => 1,
+            IssueCreateError::EmptyTitle => 2,
+            IssueCreateError::IssueTypesNotFound(_) => 3,
+            IssueCreateError::UnknownProject(_) => 4,
+            IssueCreateError::UnknownIssueType(..) => 5,
+            IssueCreateError::UnknownPriority(_) => 6,
+            IssueCreateError::UnknownComponent(..) => 7,
+            IssueCreateError::InvalidLabel(_) => 8,
+            IssueCreateError::DescriptionFile(..) => 9,
+            IssueCreateError::SelectOption => 10,
+            IssueCreateError::MissingParent(_) => 11,
+            IssueCreateError::Prompt(_) => 12,
+            IssueCreateError::PastDueDate(_) => 13,
+            IssueCreateError::UnknownSprint(..) => 14,
+            IssueCreateError::MissingBoard(_) => 15,
+            IssueCreateError::ReporterNotSettable(_) => 16,
+            IssueCreateError::InvalidSummary => 17,
+            IssueCreateError::Duplicate(..) => 18,
+            IssueCreateError::UnknownVersion(..) | IssueCreateError::ReleasedVersion(_) => 19,
+            IssueCreateError::Canceled => CANCELED_EXIT_CODE,
+        }
+    }
+
+    /// Prints the error for the user and returns the matching exit code
+    fn report(&self) -> ExitCode {
+        match self {
+            IssueCreateError::Canceled => say!("Canceled"),
+            err => say!("❌ Failed to create issue: {}", err),
+        }
+        ExitCode::from(self.exit_code())
+    }
+}
+
+trait PromptResultExt<T> {
+    /// Exits quietly when the user cancels the prompt instead of panicking
+    fn or_exit(self) -> T;
+}
+
+impl<T> PromptResultExt<T> for InquireResult<T> {
+    fn or_exit(self) -> T {
+        match self {
+            Ok(value) => value,
+            Err(InquireError::OperationCanceled | InquireError::OperationInterrupted) => {
+                say!("Canceled");
+                process::exit(CANCELED_EXIT_CODE.into());
+            }
+            Err(err) => panic!("Cannot prompt: {}", err),
+        }
+    }
 }
 
 #[tokio::main]
-async fn main() {
-    Lazy::force(&CONFIG_PATH);
+async fn main() -> ExitCode {
     let cli = Cli::parse();
-    let config = match load_config() {
+    let plain = output::no_color_requested(cli.no_color);
+    output::init(plain);
+    init_logging(cli.verbose, plain);
+    let config_path = cli
+        .config_path
+        .as_deref()
+        .map(expand_path)
+        .unwrap_or_else(|| CONFIG_PATH.clone());
+    // Runs before loading, a file that doesn't parse is what it is there to fix
+    if let Commands::Config {
+        command: Some(ConfigCommands::Edit),
+    } = cli.command
+    {
+        return edit_config(&config_path, cli.profile.as_deref());
+    }
+    let mut config_missing = false;
+    let mut config = match load_config(&config_path) {
         Ok(config) => config,
         Err(LoadConfigError::Read) => {
-            println!("Config read error, will use default config");
+            config_missing = std::env::var_os(JIRA_URL_ENV).is_none();
             Config::default()
         }
-        Err(LoadConfigError::Deserialize) => {
-            panic!("Cannot deserialize config file!");
+        Err(err @ LoadConfigError::Deserialize(_)) => {
+            say!("❌ {}", err);
+            say!("💡 Fix it with: fast-task config edit");
+            return ExitCode::FAILURE;
+        }
+        Err(err @ LoadConfigError::Keyring(_)) => {
+            say!("❌ {}", err);
+            say!("💡 Without a usable keyring set \"use_keyring\": false in the profile,");
+            say!("   the token is then kept in the config file: fast-task config edit");
+            return ExitCode::FAILURE;
+        }
+        Err(err @ LoadConfigError::Migrate(_)) => {
+            say!("❌ {}", err);
+            say!(
+                "💡 Check that {} is writable, the upgraded file is saved there",
+                config_path.display()
+            );
+            return ExitCode::FAILURE;
+        }
+        Err(err @ (LoadConfigError::TokenFile(..) | LoadConfigError::UnsupportedVersion(_))) => {
+            say!("❌ {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let profile_name = config.resolve_profile_name(cli.profile.as_deref());
+    if config_missing {
+        let offer_setup = !matches!(
+            cli.command,
+            Commands::Config { .. }
+                | Commands::Login { .. }
+                | Commands::Test { quiet: true, .. }
+                | Commands::Validate
+        ) && io::stdin().is_terminal();
+        if offer_setup {
+            let add_project = !matches!(cli.command, Commands::AddProject);
+            config = match first_run_setup(config, &config_path, &profile_name, add_project).await {
+                Ok(config) => config,
+                Err(code) => return code,
+            };
+        } else {
+            say!("Config read error, will use default config");
+        }
+    }
+    let profile = match config.profiles.get(&profile_name) {
+        Some(profile) => profile.clone(),
+        None => {
+            if cli.profile.is_some()
+                && !matches!(
+                    cli.command,
+                    Commands::Config { .. } | Commands::Profile { .. } | Commands::Template { .. }
+                )
+            {
+                say!("❌ Profile '{}' not found. Add it first:", profile_name);
+                say!("fast-task profile add {}", profile_name);
+                return ExitCode::FAILURE;
+            }
+            Profile::default()
+        }
+    };
+    let profile = match profile.with_env_overrides() {
+        Ok(profile) => profile,
+        Err(err) => {
+            say!("❌ {}", err);
+            return ExitCode::FAILURE;
         }
     };
 
     match cli.command {
-        Commands::Config => interactive_set_config(&config),
-        Commands::AddProject => interactive_add_project(&config),
+        Commands::Config { command: None } => {
+            interactive_set_config(&config, &config_path, &profile_name)
+        }
+        Commands::Config {
+            command: Some(ConfigCommands::Show { json }),
+        } => show_config(&profile, &profile_name, &config_path, json),
+        Commands::Config {
+            command: Some(ConfigCommands::Edit),
+        } => unreachable!("config edit runs before the config is loaded"),
+        Commands::Login {
+            client_id,
+            client_secret,
+            port,
+        } => {
+            login(
+                &config,
+                &config_path,
+                &profile_name,
+                &profile,
+                client_id,
+                client_secret,
+                port,
+            )
+            .await
+        }
+        Commands::AddProject => interactive_add_project(&config, &config_path, &profile_name).await,
+        Commands::RemoveProject { key } => {
+            remove_project(&config, &config_path, &profile_name, key)
+        }
         Commands::ListProjects => {
-            if config.projects.is_empty() {
-                println!("No projects configured. Use 'fast-task add-project' to add one.");
+            if profile.projects.is_empty() {
+                say!("No projects configured. Use 'fast-task add-project' to add one.");
             } else {
-                println!("Configured projects:");
-                for (key, name) in &config.projects {
-                    println!("  {} - {}", key, name);
+                say!("Configured projects:");
+                for (key, project) in &profile.projects {
+                    match project.default_issue_type {
+                        Some(ref issue_type) => {
+                            say!(
+                                "  {} - {} (default type: {})",
+                                key,
+                                project.name,
+                                issue_type
+                            )
+                        }
+                        None => say!("  {} - {}", key, project.name),
+                    }
                 }
             }
+
+            ExitCode::SUCCESS
+        }
+
+        Commands::Test {
+            quiet: true,
+            dry_run,
+        } => {
+            let connected = if dry_run {
+                !profile.jira_url.trim().is_empty()
+                    && match JiraClient::new(&profile) {
+                        Ok(client) => client.check_reachable().await.is_ok(),
+                        Err(_) => false,
+                    }
+            } else {
+                profile.is_configured()
+                    && match JiraClient::new(&profile) {
+                        Ok(client) => client.get_myself().await.is_ok(),
+                        Err(_) => false,
+                    }
+            };
+            if connected {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
         }
 
-        Commands::Test => {
-            if !config.is_configured() {
-                println!("❌ Please configure Jira connection first:");
-                println!("fast-task config ");
+        Commands::Test {
+            quiet: false,
+            dry_run,
+        } => {
+            let configured = if dry_run {
+                !profile.jira_url.trim().is_empty()
+            } else {
+                profile.is_configured()
+            };
+            if !configured {
+                say!("❌ Please configure Jira connection first:");
+                say!("fast-task config");
+                return ExitCode::FAILURE;
             }
 
-            println!("🔍 Testing Jira connection...");
-            match test_connection(&JiraClient::new(&config)).await {
-                Ok(_) => {
-                    println!("✅ Connection successful!");
-                    println!("   URL: {}", config.jira_url);
-                    println!("   Email: {}", config.email);
+            let Some(client) = connect(&config, &profile, &config_path, &profile_name) else {
+                return ExitCode::FAILURE;
+            };
+            say!("🔍 Testing Jira connection...");
+            match client.check_reachable().await {
+                Ok(status) => say!(
+                    "✅ Reachable: {} answered with {}",
+                    profile.base_url(),
+                    status
+                ),
+                Err(JiraClientError::Request(kind, message)) => {
+                    say!("❌ Not reachable: {}: {}", kind, message);
+                    print_request_hint(kind, &profile);
+                    return ExitCode::FAILURE;
                 }
                 Err(e) => {
-                    println!("❌ Connection failed: {}", e);
-                    println!("💡 Check your configuration:");
-                    println!("   - URL: {}", config.jira_url);
-                    println!("   - Email: {}", config.email);
+                    say!("❌ Not reachable: {}", e);
+                    return ExitCode::FAILURE;
                 }
             }
-        }
+            if dry_run {
+                say!("💡 Credentials were not checked, run 'fast-task test' for that");
+                return ExitCode::SUCCESS;
+            }
 
-        Commands::Create => {
-            if !config.is_configured() {
-                println!("❌ Please configure Jira connection first:");
-                println!("fast-task config");
+            match client.get_myself().await {
+                Ok(user) => {
+                    say!("✅ Connection successful!");
+                    say!("   User: {}", user.display_name);
+                    say!("   Profile: {}", profile_name);
+                    say!("   URL: {}", profile.jira_url);
+                    say!("   {}", profile.login());
+                }
+                Err(JiraClientError::Unauthorized) => {
+                    say!("❌ Connection failed: Jira rejected the credentials (401)");
+                    let (cause, fix) = match profile.auth_scheme {
+                        AuthScheme::OAuth => (
+                            "The OAuth session may have expired or been revoked",
+                            "Sign in again with 'fast-task login'",
+                        ),
+                        AuthScheme::BasicPassword => (
+                            "The password may be wrong or changed",
+                            "Enter it again with 'fast-task config'",
+                        ),
+                        AuthScheme::Basic | AuthScheme::Bearer => (
+                            "Your api token may be expired or belong to another account",
+                            "Create a new token and run 'fast-task config'",
+                        ),
+                    };
+                    say!("💡 {}:", cause);
+                    say!("   - Profile: {}", profile_name);
+                    say!("   - {}", profile.login());
+                    say!("   - Auth scheme: {}", profile.auth_scheme);
+                    say!("   {}", fix);
+                    return ExitCode::FAILURE;
+                }
+                Err(JiraClientError::Forbidden) => {
+                    say!("❌ Connection failed: access denied (403)");
+                    say!("💡 The account may lack permission to use the REST API,");
+                    say!("   or Jira wants a CAPTCHA solved after failed logins.");
+                    say!(
+                        "   Log in to {} in the browser and try again",
+                        profile.jira_url
+                    );
+                    return ExitCode::FAILURE;
+                }
+                Err(JiraClientError::Request(kind, message)) if kind != RequestErrorKind::Other => {
+                    say!("❌ Connection failed: {}: {}", kind, message);
+                    print_request_hint(kind, &profile);
+                    return ExitCode::FAILURE;
+                }
+                Err(e) => {
+                    say!("❌ Connection failed: {}", e);
+                    say!("💡 Check your configuration:");
+                    say!("   - Profile: {}", profile_name);
+                    say!("   - URL: {}", profile.jira_url);
+                    say!("   - {}", profile.login());
+                    return ExitCode::FAILURE;
+                }
             }
 
-            if config.projects.is_empty() {
-                println!("❌ No projects configured. Add one first:");
-                println!("fast-task add-project <KEY> --name <NAME>");
+            ExitCode::SUCCESS
+        }
+
+        Commands::Validate => validate_profile(&profile, &profile_name),
+
+        Commands::Whoami => {
+            if !profile.is_configured() {
+                say!("❌ Please configure Jira connection first:");
+                say!("fast-task config");
+                return ExitCode::FAILURE;
             }
 
-            match interactive_create_issue(&config).await {
-                Ok(issue_url) => {
-                    println!("✅ Issue created successfully!");
-                    println!("🔗 {}", issue_url);
+            let Some(client) = connect(&config, &profile, &config_path, &profile_name) else {
+                return ExitCode::FAILURE;
+            };
+            match client.get_myself().await {
+                Ok(user) => {
+                    say!("👤 {}", user.display_name);
+                    if let Some(ref account_id) = user.account_id {
+                        say!("   Account id: {}", account_id);
+                    }
+                    if let Some(ref name) = user.name {
+                        say!("   Username: {}", name);
+                    }
+                    if let Some(ref email) = user.email_address {
+                        say!("   Email: {}", email);
+                    }
+                    say!("   Profile: {}", profile_name);
                 }
                 Err(e) => {
-                    println!("❌ Failed to create issue: {}", e);
+                    say!("❌ Failed to fetch the current user: {}", e);
+                    return ExitCode::FAILURE;
                 }
             }
+
+            ExitCode::SUCCESS
         }
-    }
-}
 
-fn interactive_set_config(original_config: &Config) {
-    println!("🎯 Setup a jira configuration\n");
+        Commands::Create(args) => {
+            let args = *args;
+            output::set_quiet(args.quiet);
+            output::set_stderr(args.json);
+            if !profile.is_configured() {
+                say!("❌ Please configure Jira connection first:");
+                say!("fast-task config");
+                return ExitCode::FAILURE;
+            }
 
-    let mut jira_url: String;
-    let mut email: String;
-    let mut api_token: String;
+            if profile.projects.is_empty() {
+                say!("❌ No projects configured. Add one first:");
+                say!("fast-task add-project");
+                return ExitCode::FAILURE;
+            }
 
-    loop {
-        jira_url = Text::new("Jira URL:")
-            .with_help_message("Enter your Jira instance URL (include https://)")
-            .with_placeholder("e.g., https://company.atlassian.net")
-            .prompt()
-            .expect("Cannot prompt");
+            let mut args = match args.template {
+                Some(ref name) => match config.templates.get(name) {
+                    Some(template) => args.with_template(template),
+                    None => {
+                        say!("❌ Template '{}' not found", name);
+                        say!("fast-task template list");
+                        return ExitCode::FAILURE;
+                    }
+                },
+                None => args,
+            };
+            if !args.no_template {
+                args.description_template = config.description_template.clone();
+            }
+            if let Err(e) = args
+                .read_description_file()
+                .and_then(|_| args.check_due_date())
+            {
+                return e.report();
+            }
+            if let Some(ref project) = args.project {
+                match resolve_project(&profile, project) {
+                    Ok(key) => args.project = Some(key),
+                    Err(e) => return e.report(),
+                }
+            }
 
-        if !jira_url.validate_url() {
-            println!("❌ jira url is not valid. Try again");
-            continue;
-        }
-        break;
-    }
+            let idempotency = IdempotencyStore::new(&config_path, &profile_name);
+            if let Some(ref key) = args.idempotency_key
+                && !args.dry_run
+                && let Some(issue) = idempotency.get(key)
+            {
+                if args.json {
+                    println!("{}", json!(issue));
+                } else if args.quiet {
+                    println!("{}", args.quiet_result(&issue));
+                } else {
+                    say!(
+                        "✅ Issue {} was already created with idempotency key '{}'",
+                        issue.key,
+                        key
+                    );
+                    say!("🔗 {}", issue.url);
+                    if args.raw_url && !issue.self_url.is_empty() {
+                        say!("🔌 {}", issue.self_url);
+                    }
+                }
+                return ExitCode::SUCCESS;
+            }
 
-    loop {
-        email = Text::new("Your Jira email:")
-            .with_help_message("Enter your email address for Jira authentication")
-            .with_placeholder("user@company.com")
-            .prompt()
-            .expect("Cannot prompt");
+            let Some(client) = connect(&config, &profile, &config_path, &profile_name) else {
+                return ExitCode::FAILURE;
+            };
+            let client = client.with_issue_type_cache(IssueTypeCache::new(
+                &config_path,
+                Duration::from_secs(config.issue_types_cache_ttl_secs),
+                args.refresh,
+            ));
+            if let Some(assignee) = args.assignee.take() {
+                match resolve_me(&client, &assignee).await {
+                    Ok(assignee) => args.assignee = Some(assignee),
+                    Err(e) => {
+                        say!("❌ Could not look up your account: {}", e);
+                        return ExitCode::FAILURE;
+                    }
+                }
+            }
+            let result = match (&args.project, &args.title, args.issue_type(&profile)) {
+                (Some(project), Some(title), Some(ref issue_type)) if cli.yes => {
+                    create_issue_noninteractive(&client, project, title, issue_type, &args).await
+                }
+                _ => {
+                    interactive_create_issue(
+                        &client,
+                        &args,
+                        cli.yes,
+                        config.last_project.as_deref(),
+                    )
+                    .await
+                }
+            };
 
-        if !email.validate_email() {
-            println!("❌ Email is not valid. Try again");
-            continue;
-        }
-        break;
-    }
-    loop {
-        api_token = Text::new("Your Jira api token:")
-            .with_help_message("Enter your api token")
-            .prompt()
-            .expect("Cannot prompt");
+            match result {
+                Ok(None) => {
+                    say!("✅ Dry run finished, no issue was created");
+                }
+                Ok(Some(issue)) if args.json => {
+                    remember_idempotency_key(&idempotency, &args, &issue);
+                    remember_last_project(&config, &config_path, &profile_name, &issue.key);
+                    record_history(&config_path, &issue);
+                    if args.wait || args.wait_and_open {
+                        wait_for_issue(&client, &issue.key).await;
+                    }
+                    if args.start {
+                        start_issue(&client, &issue.key).await;
+                    }
+                    println!("{}", json!(issue));
+                }
+                Ok(Some(issue)) if args.quiet => {
+                    remember_idempotency_key(&idempotency, &args, &issue);
+                    remember_last_project(&config, &config_path, &profile_name, &issue.key);
+                    record_history(&config_path, &issue);
+                    if args.wait || args.wait_and_open {
+                        wait_for_issue(&client, &issue.key).await;
+                    }
+                    println!("{}", args.quiet_result(&issue));
+                    if args.start {
+                        start_issue(&client, &issue.key).await;
+                    }
+                    if (args.open || args.wait_and_open || config.auto_open)
+                        && let Err(e) = open::that(&issue.url)
+                    {
+                        esay!("⚠️ Could not open a browser ({})", e);
+                    }
+                }
+                Ok(Some(issue)) => {
+                    say!("✅ Issue created successfully!");
+                    say!("🔗 {}", issue.url);
+                    if args.raw_url {
+                        say!("🔌 {}", issue.self_url);
+                    }
+                    remember_idempotency_key(&idempotency, &args, &issue);
+                    remember_last_project(&config, &config_path, &profile_name, &issue.key);
+                    record_history(&config_path, &issue);
+                    if (args.wait || args.wait_and_open)
+                        && wait_for_issue(&client, &issue.key).await
+                    {
+                        say!("✅ {} is available", issue.key);
+                    }
+                    if args.start
+                        && let Some(transition) = start_issue(&client, &issue.key).await
+                    {
+                        say!("▶️ {} moved via '{}'", issue.key, transition);
+                    }
+                    if (args.open || args.wait_and_open || config.auto_open)
+                        && let Err(e) = open::that(&issue.url)
+                    {
+                        say!("⚠️ Could not open a browser ({}), use the link above", e);
+                    }
+                }
+                Err(e) => return e.report(),
+            }
 
-        if api_token.trim().is_empty() {
-            println!("❌ Api token cannot be empty. Try again");
-            continue;
-        }
-        break;
-    }
-    let config = Config::new(jira_url, email, api_token, original_config.projects.clone());
-    match save_config(config) {
-        Ok(_) => {
-            println!("Configuration saved!");
+            ExitCode::SUCCESS
         }
-        Err(err) => {
-            println!("Failed to save config: {}", err);
-        }
-    }
-}
 
-fn interactive_add_project(original_config: &Config) {
-    let mut project_key: String;
-    let mut project_name: String;
+        Commands::BulkCreate {
+            file,
+            project,
+            issue_type,
+            concurrency,
+            no_default_labels,
+            allow_unknown_projects,
+        } => {
+            if !profile.is_configured() {
+                say!("❌ Please configure Jira connection first:");
+                say!("fast-task config");
+                return ExitCode::FAILURE;
+            }
 
-    loop {
-        project_key = Text::new("Your project key:")
-            .with_help_message("Enter your project key")
-            .with_placeholder("e.g. PRKEY")
-            .prompt()
-            .expect("Cannot prompt");
+            // Lines without a "project" need this one
+            let default_project = match project {
+                Some(project) => match resolve_project(&profile, &project) {
+                    Ok(key) => Some(key),
+                    Err(IssueCreateError::UnknownProject(_)) if allow_unknown_projects => {
+                        Some(project)
+                    }
+                    Err(e) => return e.report(),
+                },
+                None if profile.projects.len() == 1 => profile.projects.keys().next().cloned(),
+                None => None,
+            };
 
-        if project_key.trim().is_empty() {
-            println!("❌ Project key cannot be empty. Try again");
-            continue;
+            let Some(client) = connect(&config, &profile, &config_path, &profile_name) else {
+                return ExitCode::FAILURE;
+            };
+            let client = client.with_issue_type_cache(IssueTypeCache::new(
+                &config_path,
+                Duration::from_secs(config.issue_types_cache_ttl_secs),
+                false,
+            ));
+            bulk_create(
+                &client,
+                &config_path,
+                &file,
+                &BulkOptions {
+                    default_project: default_project.as_deref(),
+                    default_issue_type: issue_type.as_deref(),
+                    concurrency: concurrency.into(),
+                    no_default_labels,
+                    allow_unknown_projects,
+                },
+            )
+            .await
         }
-        break;
-    }
-    loop {
-        project_name = Text::new("Your Jira project name:")
-            .with_help_message("Enter name of your project (for display)")
-            .prompt()
-            .expect("Cannot prompt");
 
-        if project_name.trim().is_empty() {
-            println!("❌ Project name cannot be empty. Try again");
-            continue;
-        }
-        break;
-    }
-    let mut projects = original_config.projects.clone();
-    projects.insert(project_key, project_name);
-    match save_config(Config::new(
-        original_config.jira_url.clone(),
-        original_config.email.clone(),
-        original_config.api_token.clone(),
-        projects,
-    )) {
-        Ok(_) => {
-            println!("Configuration saved!");
-        }
-        Err(err) => {
-            println!("Failed to save config: {}", err);
-        }
-    }
-}
+        Commands::ListIssues {
+            project,
+            status,
+            limit,
+            all,
+            output,
+        } => {
+            if !profile.is_configured() {
+                say!("❌ Please configure Jira connection first:");
+                say!("fast-task config");
+                return ExitCode::FAILURE;
+            }
 
-async fn interactive_create_issue(config: &Config) -> Result<String, IssueCreateError> {
-    println!("🎯 Creating a new Jira issue \n");
+            let project = match project {
+                Some(project) => project,
+                None => {
+                    let mut project_options: Vec<String> =
+                        profile.projects.keys().cloned().collect();
+                    match project_options.len() {
+                        0 => {
+                            say!("❌ No projects configured. Use --project or add one first:");
+                            say!("fast-task add-project");
+                            return ExitCode::FAILURE;
+                        }
+                        1 => project_options.remove(0),
+                        _ => Select::new("Which project?", project_options)
+                            .prompt()
+                            .or_exit(),
+                    }
+                }
+            };
 
-    let client = JiraClient::new(config);
-    let project_options: Vec<String> = config.projects.keys().cloned().collect();
-    let selected_project = Select::new("Which project?", project_options)
-        .with_help_message("Select the project where you want to create the issue")
-        .prompt()
-        .expect("Cannot prompt");
+            let mut jql = format!("project = {}", jql_string(&project));
+            if let Some(status) = status {
+                jql.push_str(&format!(" AND status = {}", jql_string(&status)));
+            }
+            jql.push_str(" ORDER BY created DESC");
 
-    println!(
-        "✓ Selected project: {} ({})",
-        selected_project,
-        config
-            .projects
-            .get(&selected_project)
-            .unwrap_or(&selected_project)
-    );
+            let Some(client) = connect(&config, &profile, &config_path, &profile_name) else {
+                return ExitCode::FAILURE;
+            };
+            let limit = (!all).then_some(limit);
+            match collect_issues(limit, |start_at, max_results| {
+                client.search_issues(&jql, start_at, max_results)
+            })
+            .await
+            {
+                Ok(issues) if issues.is_empty() && output == OutputFormat::Table => {
+                    say!("No issues found in project {}", project);
+                }
+                Ok(issues) => print_issues(&issues, output),
+                Err(e) => {
+                    say!("❌ Failed to list issues: {}", e);
+                    return ExitCode::FAILURE;
+                }
+            }
 
-    let title = Text::new("Issue title:")
-        .with_help_message("Enter a brief, descriptive title for your issue")
-        .with_placeholder("e.g., Fix login button styling")
-        .prompt()
-        .expect("Cannot prompt");
+            ExitCode::SUCCESS
+        }
 
-    if title.trim().is_empty() {
-        return Err(IssueCreateError::EmptyTitle);
-    }
+        Commands::MyIssues {
+            since,
+            status,
+            limit,
+            all,
+            output,
+        } => {
+            if !profile.is_configured() {
+                say!("❌ Please configure Jira connection first:");
+                say!("fast-task config");
+                return ExitCode::FAILURE;
+            }
 
-    let has_description = Confirm::new("Add description?")
-        .with_default(false)
-        .with_help_message("Press 'y' to add a detailed description")
-        .prompt()
-        .expect("Cannot prompt");
+            let mut jql = String::from("assignee = currentUser()");
+            if !profile.projects.is_empty() {
+                let projects: Vec<String> =
+                    profile.projects.keys().map(|key| jql_string(key)).collect();
+                jql.push_str(&format!(" AND project in ({})", projects.join(", ")));
+            }
+            if let Some(status) = status {
+                jql.push_str(&format!(" AND status = {}", jql_string(&status)));
+            }
+            if let Some(since) = since {
+                jql.push_str(&format!(" AND updated >= -{}", since));
+            }
+            jql.push_str(" ORDER BY updated DESC");
 
-    let description = if has_description {
-        let desc = Text::new("Issue description:")
-            .with_help_message("Provide detailed information about the issue")
-            .with_placeholder("Steps to reproduce, expected behavior, etc.")
-            .prompt()
-            .expect("Cannot prompt");
+            let Some(client) = connect(&config, &profile, &config_path, &profile_name) else {
+                return ExitCode::FAILURE;
+            };
+            let limit = (!all).then_some(limit);
+            match collect_issues(limit, |start_at, max_results| {
+                client.search_issues(&jql, start_at, max_results)
+            })
+            .await
+            {
+                Ok(issues) if issues.is_empty() && output == OutputFormat::Table => {
+                    say!("No issues assigned to you");
+                }
+                Ok(issues) => print_issues(&issues, output),
+                Err(e) => {
+                    say!("❌ Failed to list issues: {}", e);
+                    return ExitCode::FAILURE;
+                }
+            }
 
-        if desc.trim().is_empty() {
-            None
-        } else {
-            Some(desc)
+            ExitCode::SUCCESS
         }
-    } else {
-        None
-    };
 
-    println!(
-        "🔍 Fetching available issue types for project {}...",
-        selected_project
-    );
+        Commands::Search {
+            jql,
+            limit,
+            all,
+            fields,
+            json,
+            output,
+        } => {
+            if !profile.is_configured() {
+                say!("❌ Please configure Jira connection first:");
+                say!("fast-task config");
+                return ExitCode::FAILURE;
+            }
 
-    let issue_types = match get_project_issue_types(&client, &selected_project).await {
-        Ok(types) => {
-            if types.is_empty() {
-                return Err(IssueCreateError::IssueTypesNotFound(selected_project));
+            let Some(client) = connect(&config, &profile, &config_path, &profile_name) else {
+                return ExitCode::FAILURE;
+            };
+            let limit = (!all).then_some(limit);
+            let result = if fields.is_empty() && !json {
+                collect_issues(limit, |start_at, max_results| {
+                    client.search_issues(&jql, start_at, max_results)
+                })
+                .await
+                .map(|issues| print_issues(&issues, output))
             } else {
-                println!(
-                    "✅ Found {} issue type(s) for project {selected_project}",
-                    types.len()
-                );
-                types
+                let mut requested: Vec<&str> = vec!["summary"];
+                if fields.is_empty() {
+                    requested.extend(["status", "assignee"]);
+                }
+                requested.extend(fields.iter().map(|field| field.trim()));
+                collect_issues(limit, |start_at, max_results| {
+                    client.search_issues_raw(&jql, start_at, max_results, &requested)
+                })
+                .await
+                .map(|issues| {
+                    // With --fields the JSON output is Jira's own, as with --json
+                    if json || output == OutputFormat::Json {
+                        let issues = Value::Array(issues);
+                        println!(
+                            "{}",
+                            serde_json::to_string_pretty(&issues)
+                                .unwrap_or_else(|_| issues.to_string())
+                        );
+                    } else {
+                        print_fields_table(&issues, &fields, output);
+                    }
+                })
+            };
+
+            match result {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(JiraClientError::JiraValidationError { messages, errors }) => {
+                    say!("❌ Jira could not run the query:");
+                    for message in messages.iter().chain(errors.values()) {
+                        say!("   {}", message);
+                    }
+                    ExitCode::FAILURE
+                }
+                Err(e) => {
+                    say!("❌ Failed to search issues: {}", e);
+                    ExitCode::FAILURE
+                }
             }
         }
-        Err(e) => {
-            return Err(IssueCreateError::JiraClient(
-                selected_project,
-                format!("Jira client error: {}", e),
-            ));
+
+        Commands::View { key, json } => {
+            if !profile.is_configured() {
+                say!("❌ Please configure Jira connection first:");
+                say!("fast-task config");
+                return ExitCode::FAILURE;
+            }
+
+            let Some(client) = connect(&config, &profile, &config_path, &profile_name) else {
+                return ExitCode::FAILURE;
+            };
+            if json {
+                match client.get_issue(&key, &[]).await {
+                    Ok(issue) => println!(
+                        "{}",
+                        serde_json::to_string_pretty(&issue).unwrap_or_else(|_| issue.to_string())
+                    ),
+                    Err(e) => {
+                        say!("❌ Failed to fetch {}: {}", key, e);
+                        return ExitCode::FAILURE;
+                    }
+                }
+                return ExitCode::SUCCESS;
+            }
+            match client.get_issue_details(&key).await {
+                Ok(issue) => print_issue_card(&issue, &client.profile().issue_url(&issue.key)),
+                Err(e) => {
+                    say!("❌ Failed to fetch {}: {}", key, e);
+                    return ExitCode::FAILURE;
+                }
+            }
+
+            ExitCode::SUCCESS
         }
-    };
 
-    let issue_type_options: Vec<String> = issue_types
-        .iter()
-        .map(|it| {
-            if let Some(ref description) = it.description {
-                let desc = if description.len() > 60 {
-                    format!("{}...", &description[..57])
-                } else {
-                    description.clone()
-                };
-                format!("{} - {}", it.name, desc)
-            } else {
-                it.name.clone()
+        Commands::Transition { key, to } => {
+            if !profile.is_configured() {
+                say!("❌ Please configure Jira connection first:");
+                say!("fast-task config");
+                return ExitCode::FAILURE;
             }
-        })
-        .collect();
 
-    let selected_option = Select::new("Issue type:", issue_type_options.clone())
-        .with_help_message("Select the type of issue you're creating")
-        .prompt()
-        .expect("Cannot prompt");
+            let Some(client) = connect(&config, &profile, &config_path, &profile_name) else {
+                return ExitCode::FAILURE;
+            };
+            transition_issue(&client, &key, to.as_deref()).await
+        }
 
-    let selected_index = issue_type_options
-        .iter()
-        .position(|option| option == &selected_option)
-        .ok_or(IssueCreateError::SelectOption)?;
+        Commands::Delete {
+            key,
+            delete_subtasks,
+            force,
+        } => {
+            if !profile.is_configured() {
+                say!("❌ Please configure Jira connection first:");
+                say!("fast-task config");
+                return ExitCode::FAILURE;
+            }
 
-    let selected_issue_type = &issue_types[selected_index];
+            let Some(client) = connect(&config, &profile, &config_path, &profile_name) else {
+                return ExitCode::FAILURE;
+            };
+            let issue = match client.get_issue_details(&key).await {
+                Ok(issue) => issue,
+                Err(e) => {
+                    say!("❌ Failed to fetch {}: {}", key, e);
+                    return ExitCode::FAILURE;
+                }
+            };
 
-    println!("\n📋 Issue Summary:");
-    println!(
-        "   Project: {} ({})",
-        selected_project,
-        config
-            .projects
-            .get(&selected_project)
-            .unwrap_or(&selected_project)
-    );
-    println!("   Title: {}", title);
-    if let Some(ref desc) = description {
-        println!(
-            "   Description: {}",
-            if desc.chars().count() > 50 {
-                format!("{}...", desc.chars().take(50).collect::<String>())
-            } else {
-                desc.clone()
+            let confirm = force
+                || Confirm::new(&format!(
+                    "Delete {} \"{}\"{}? This cannot be undone",
+                    issue.key,
+                    issue.summary,
+                    if delete_subtasks {
+                        " with its sub-tasks"
+                    } else {
+                        ""
+                    }
+                ))
+                .with_default(false)
+                .prompt()
+                .or_exit();
+            if !confirm {
+                say!("Nothing deleted");
+                return ExitCode::SUCCESS;
             }
-        );
-    }
-    println!("   Type: {}", selected_issue_type.name);
-    if let Some(ref desc) = selected_issue_type.description {
-        println!("   Type Description: {}", desc);
-    }
 
-    let confirm = Confirm::new("Create this issue?")
-        .with_default(true)
-        .prompt()
-        .expect("Cannot prompt");
+            match client.delete_issue(&issue.key, delete_subtasks).await {
+                Ok(()) => {
+                    say!("🗑️ {} deleted", issue.key);
+                    ExitCode::SUCCESS
+                }
+                Err(JiraClientError::Forbidden) => {
+                    say!(
+                        "❌ You don't have permission to delete {}, ask a Jira admin for the Delete Issues permission",
+                        issue.key
+                    );
+                    ExitCode::FAILURE
+                }
+                Err(e @ JiraClientError::JiraValidationError { .. }) if !delete_subtasks => {
+                    say!("❌ Failed to delete {}: {}", issue.key, e);
+                    say!("💡 If the issue has sub-tasks, pass --delete-subtasks");
+                    ExitCode::FAILURE
+                }
+                Err(e) => {
+                    say!("❌ Failed to delete {}: {}", issue.key, e);
+                    ExitCode::FAILURE
+                }
+            }
+        }
+
+        Commands::Link {
+            from,
+            to,
+            link_type,
+        } => {
+            if !profile.is_configured() {
+                say!("❌ Please configure Jira connection first:");
+                say!("fast-task config");
+                return ExitCode::FAILURE;
+            }
+
+            let Some(client) = connect(&config, &profile, &config_path, &profile_name) else {
+                return ExitCode::FAILURE;
+            };
+            link_issues(&client, &from, &to, link_type.as_deref()).await
+        }
+
+        Commands::Comment { key, body } => {
+            if !profile.is_configured() {
+                say!("❌ Please configure Jira connection first:");
+                say!("fast-task config");
+                return ExitCode::FAILURE;
+            }
+
+            let body = body.unwrap_or_else(prompt_multiline_comment);
+            if body.trim().is_empty() {
+                say!("❌ Comment cannot be empty");
+                return ExitCode::FAILURE;
+            }
+
+            let Some(client) = connect(&config, &profile, &config_path, &profile_name) else {
+                return ExitCode::FAILURE;
+            };
+            match client.add_comment(&key, &body).await {
+                Ok(comment_url) => {
+                    say!("✅ Comment added!");
+                    say!("🔗 {}", comment_url);
+                }
+                Err(e) => {
+                    say!("❌ Failed to add comment: {}", e);
+                    return ExitCode::FAILURE;
+                }
+            }
+
+            ExitCode::SUCCESS
+        }
+
+        Commands::Update {
+            key,
+            summary,
+            description,
+            assignee,
+            priority,
+        } => {
+            if !profile.is_configured() {
+                say!("❌ Please configure Jira connection first:");
+                say!("fast-task config");
+                return ExitCode::FAILURE;
+            }
+
+            let Some(client) = connect(&config, &profile, &config_path, &profile_name) else {
+                return ExitCode::FAILURE;
+            };
+            let mut fields = serde_json::Map::new();
+            if let Some(summary) = summary {
+                fields.insert("summary".to_string(), json!(summary));
+            }
+            if let Some(description) = description {
+                fields.insert("description".to_string(), client.rich_text(&description));
+            }
+            if let Some(assignee) = assignee {
+                let assignee = match resolve_me(&client, &assignee).await {
+                    Ok(assignee) => assignee,
+                    Err(e) => {
+                        say!("❌ Could not look up your account: {}", e);
+                        return ExitCode::FAILURE;
+                    }
+                };
+                fields.insert("assignee".to_string(), client.user_field(&assignee));
+            }
+            if let Some(priority) = priority {
+                match resolve_priority(&client, &key, &priority).await {
+                    Ok(priority) => {
+                        fields.insert("priority".to_string(), json!({ "id": priority.id }));
+                    }
+                    Err(e) => {
+                        say!("❌ {}", e);
+                        return ExitCode::FAILURE;
+                    }
+                }
+            }
+
+            if fields.is_empty() {
+                say!(
+                    "Nothing to update. Pass at least one of --summary, --description, --assignee, --priority"
+                );
+                return ExitCode::FAILURE;
+            }
+
+            match client.update_issue(&key, Value::Object(fields)).await {
+                Ok(_) => {
+                    say!("✅ {} updated", key);
+                    say!("🔗 {}", client.profile().issue_url(&key));
+                }
+                Err(e) => {
+                    say!("❌ Failed to update {}: {}", key, e);
+                    return ExitCode::FAILURE;
+                }
+            }
+
+            ExitCode::SUCCESS
+        }
+
+        Commands::Attach { key, files } => {
+            if !profile.is_configured() {
+                say!("❌ Please configure Jira connection first:");
+                say!("fast-task config");
+                return ExitCode::FAILURE;
+            }
+
+            let Some(client) = connect(&config, &profile, &config_path, &profile_name) else {
+                return ExitCode::FAILURE;
+            };
+            attach_files(&client, &key, &files).await
+        }
+
+        Commands::History { project, limit } => {
+            let entries = match read_history(&config_path) {
+                Ok(entries) => entries,
+                Err(err) => {
+                    say!("❌ Failed to read the history log: {}", err);
+                    return ExitCode::FAILURE;
+                }
+            };
+            let entries: Vec<HistoryEntry> = entries
+                .into_iter()
+                .rev()
+                .filter(|entry| {
+                    project
+                        .as_ref()
+                        .is_none_or(|project| entry.project.eq_ignore_ascii_case(project))
+                })
+                .take(limit)
+                .collect();
+
+            if entries.is_empty() {
+                say!("No created issues found in the history");
+            } else {
+                print_history(&entries);
+            }
+            ExitCode::SUCCESS
+        }
+
+        Commands::Boards { project, assign } => {
+            if !profile.is_configured() {
+                say!("❌ Please configure Jira connection first:");
+                say!("fast-task config");
+                return ExitCode::FAILURE;
+            }
+
+            let Some(client) = connect(&config, &profile, &config_path, &profile_name) else {
+                return ExitCode::FAILURE;
+            };
+            list_boards(
+                &client,
+                &config,
+                &config_path,
+                &profile_name,
+                project.as_deref(),
+                assign,
+            )
+            .await
+        }
+
+        Commands::Projects {
+            command: ProjectsCommands::Rename { key, name },
+        } => rename_project(&config, &config_path, &profile_name, &key, name),
+        Commands::Projects {
+            command: ProjectsCommands::Sync { filter, replace },
+        } => {
+            sync_projects(
+                &config,
+                &config_path,
+                &profile_name,
+                &profile,
+                filter.as_deref(),
+                replace,
+            )
+            .await
+        }
+
+        Commands::Cache { command } => match command {
+            CacheCommands::Clear => match clear_cache(&config_path) {
+                Ok(_) => {
+                    say!("Cache cleared");
+                    ExitCode::SUCCESS
+                }
+                Err(err) => {
+                    say!("Failed to clear cache: {}", err);
+                    ExitCode::FAILURE
+                }
+            },
+        },
+
+        Commands::Template { command } => match command {
+            TemplateCommands::List => {
+                if config.templates.is_empty() {
+                    say!("No templates configured. Use 'fast-task template add' to add one.");
+                } else {
+                    say!("Configured templates:");
+                    for (name, template) in &config.templates {
+                        say!("  {}", name);
+                        print_template(template);
+                    }
+                }
+                ExitCode::SUCCESS
+            }
+            TemplateCommands::Add {
+                name,
+                project,
+                issue_type,
+                priority,
+                labels,
+                components,
+                summary_prefix,
+            } => {
+                let labels = match validate_labels(&labels) {
+                    Ok(labels) => labels,
+                    Err(e) => {
+                        say!("❌ {}", e);
+                        return ExitCode::FAILURE;
+                    }
+                };
+                let template = IssueTemplate {
+                    project,
+                    issue_type,
+                    priority,
+                    labels,
+                    components,
+                    summary_prefix,
+                };
+                let mut config = config;
+                let replaced = config.templates.insert(name.clone(), template).is_some();
+                match save_config(config, &config_path) {
+                    Ok(_) => {
+                        if replaced {
+                            say!("Template '{}' updated", name);
+                        } else {
+                            say!("Template '{}' added", name);
+                        }
+                        ExitCode::SUCCESS
+                    }
+                    Err(err) => {
+                        say!("Failed to save config: {}", err);
+                        ExitCode::FAILURE
+                    }
+                }
+            }
+            TemplateCommands::Remove { name } => {
+                let mut config = config;
+                if config.templates.remove(&name).is_none() {
+                    say!("❌ Template '{}' not found", name);
+                    return ExitCode::FAILURE;
+                }
+                match save_config(config, &config_path) {
+                    Ok(_) => {
+                        say!("Template '{}' removed", name);
+                        ExitCode::SUCCESS
+                    }
+                    Err(err) => {
+                        say!("Failed to save config: {}", err);
+                        ExitCode::FAILURE
+                    }
+                }
+            }
+        },
+
+        Commands::Token {
+            command: TokenCommands::Rotate { restore },
+        } => rotate_token(&config, &config_path, &profile_name, &profile, restore).await,
+
+        Commands::Profile { command } => match command {
+            ProfileCommands::Add { name } => {
+                if config.profiles.contains_key(&name) {
+                    say!("❌ Profile '{}' already exists", name);
+                    say!("Use 'fast-task config --profile {}' to change it", name);
+                    ExitCode::FAILURE
+                } else {
+                    interactive_set_config(&config, &config_path, &name)
+                }
+            }
+            ProfileCommands::List => {
+                if config.profiles.is_empty() {
+                    say!("No profiles configured. Use 'fast-task config' to add one.");
+                } else {
+                    say!("Configured profiles:");
+                    for (name, profile) in &config.profiles {
+                        let marker = if *name == config.default_profile {
+                            "*"
+                        } else {
+                            " "
+                        };
+                        say!("{} {} - {}", marker, name, profile.jira_url);
+                    }
+                }
+                ExitCode::SUCCESS
+            }
+            ProfileCommands::Switch { name } => {
+                if !config.profiles.contains_key(&name) {
+                    say!("❌ Profile '{}' not found", name);
+                    return ExitCode::FAILURE;
+                }
+                let mut config = config;
+                config.default_profile = name.clone();
+                match save_config(config, &config_path) {
+                    Ok(_) => {
+                        say!("Default profile switched to '{}'", name);
+                        ExitCode::SUCCESS
+                    }
+                    Err(err) => {
+                        say!("Failed to save config: {}", err);
+                        ExitCode::FAILURE
+                    }
+                }
+            }
+        },
+    }
+}
+
+fn print_template(template: &IssueTemplate) {
+    if let Some(ref project) = template.project {
+        say!("    Project: {}", project);
+    }
+    if let Some(ref issue_type) = template.issue_type {
+        say!("    Type: {}", issue_type);
+    }
+    if let Some(ref priority) = template.priority {
+        say!("    Priority: {}", priority);
+    }
+    if !template.labels.is_empty() {
+        say!("    Labels: {}", template.labels.join(", "));
+    }
+    if !template.components.is_empty() {
+        say!("    Components: {}", template.components.join(", "));
+    }
+    if let Some(ref prefix) = template.summary_prefix {
+        say!("    Title prefix: {}", prefix);
+    }
+}
+
+/// Builds the Jira client, printing why when the connection settings are invalid
+fn connect(
+    config: &Config,
+    profile: &Profile,
+    config_path: &Path,
+    profile_name: &str,
+) -> Option<JiraClient> {
+    match JiraClient::new(profile) {
+        Ok(client) => Some(
+            client
+                .with_token_store(config_path, profile_name)
+                .with_min_request_interval(Duration::from_millis(config.min_request_interval_ms)),
+        ),
+        Err(e) => {
+            say!("❌ {}", e);
+            None
+        }
+    }
+}
+
+/// Explains a transport failure of `test`, `Other` has nothing more to add
+fn print_request_hint(kind: RequestErrorKind, profile: &Profile) {
+    match kind {
+        RequestErrorKind::Dns => {
+            say!("💡 The hostname couldn't be resolved, check the URL:");
+            say!("   - URL: {}", profile.jira_url);
+        }
+        RequestErrorKind::ConnectionRefused => {
+            say!("💡 Nothing accepted the connection, check the URL and port");
+            say!("   or the proxy settings:");
+            say!("   - URL: {}", profile.jira_url);
+        }
+        RequestErrorKind::Tls => {
+            say!("💡 The TLS handshake failed. For an internal CA point");
+            say!("   ca_cert_path in the config to its PEM file");
+        }
+        RequestErrorKind::Timeout => {
+            say!(
+                "💡 Jira didn't answer in {}s, check the network",
+                profile.timeout_secs
+            );
+            say!("   or raise timeout_secs in the config");
+        }
+        RequestErrorKind::Other => {}
+    }
+}
+
+fn init_logging(verbose: u8, plain: bool) {
+    let level = match verbose {
+        0 => return,
+        1 => Level::INFO,
+        2 => Level::DEBUG,
+        _ => Level::TRACE,
+    };
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_target(false)
+                .with_ansi(!plain)
+                .with_writer(std::io::stderr),
+        )
+        .with(Targets::new().with_target(env!("CARGO_CRATE_NAME"), level))
+        .init();
+}
+
+/// Connection variables that are set and therefore override the config file
+fn env_overrides() -> Vec<&'static str> {
+    [JIRA_URL_ENV, EMAIL_ENV, API_TOKEN_FILE_ENV, API_TOKEN_ENV]
+        .into_iter()
+        .filter(|name| env::var_os(name).is_some_and(|value| !value.is_empty()))
+        .collect()
+}
+
+/// Keeps the last 4 characters so tokens can be told apart
+fn mask_token(token: &str) -> String {
+    let chars: Vec<char> = token.chars().collect();
+    match chars.len() {
+        0 => String::new(),
+        len if len <= 8 => "****".to_string(),
+        len => format!("****{}", chars[len - 4..].iter().collect::<String>()),
+    }
+}
+
+fn show_config(profile: &Profile, profile_name: &str, config_path: &Path, json: bool) -> ExitCode {
+    let mut project_keys: Vec<&String> = profile.projects.keys().collect();
+    project_keys.sort();
+    let overrides = env_overrides();
+
+    if json {
+        let projects: Vec<Value> = project_keys
+            .iter()
+            .map(|key| {
+                let project = &profile.projects[*key];
+                json!({
+                    "key": key,
+                    "name": project.name,
+                    "default_issue_type": project.default_issue_type,
+                    "board_id": project.board_id,
+                    "default_labels": project.default_labels,
+                    "default_assignee": project.default_assignee,
+                })
+            })
+            .collect();
+        let value = json!({
+            "config_path": config_path,
+            "profile": profile_name,
+            "jira_url": profile.jira_url,
+            "email": profile.email,
+            "api_token": mask_token(&profile.api_token),
+            "api_token_file": profile.api_token_file,
+            "auth_scheme": profile.auth_scheme.to_string(),
+            "username": profile.username,
+            "password": if profile.password.is_empty() { "" } else { "****" },
+            "api_version": profile.api_version,
+            "timeout_secs": profile.timeout_secs,
+            "projects": projects,
+            "env_overrides": overrides,
+        });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&value).unwrap_or_else(|_| value.to_string())
+        );
+        return ExitCode::SUCCESS;
+    }
+
+    let or_unset = |value: &str| {
+        if value.is_empty() {
+            "(not set)".to_string()
+        } else {
+            value.to_string()
+        }
+    };
+    say!("Config file: {}", config_path.display());
+    say!("Profile: {}", profile_name);
+    say!("  Jira URL: {}", or_unset(&profile.jira_url));
+    if profile.auth_scheme == AuthScheme::BasicPassword {
+        say!("  Username: {}", or_unset(&profile.username));
+        // Unlike tokens, no part of a password is shown
+        say!(
+            "  Password: {}",
+            if profile.password.is_empty() {
+                "(not set)"
+            } else {
+                "****"
+            }
+        );
+    } else {
+        say!("  Email: {}", or_unset(&profile.email));
+        say!("  Api token: {}", or_unset(&mask_token(&profile.api_token)));
+        if let Some(ref path) = profile.api_token_file {
+            say!("  Api token file: {}", path.display());
+        }
+    }
+    say!("  Auth scheme: {}", profile.auth_scheme);
+    say!("  Api version: {}", profile.api_version);
+    say!("  Timeout: {}s", profile.timeout_secs);
+    if project_keys.is_empty() {
+        say!("  Projects: none");
+    } else {
+        say!("  Projects:");
+        for key in project_keys {
+            say!("    {} - {}", key, profile.projects[key].name);
+        }
+    }
+    if !overrides.is_empty() {
+        say!("Overridden by the environment: {}", overrides.join(", "));
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// Offline checks of the settings a connection needs, prints one line per check
+fn validate_profile(profile: &Profile, profile_name: &str) -> ExitCode {
+    let mut checks: Vec<(bool, String)> = Vec::new();
+
+    checks.push(match normalize_base_url(&profile.jira_url) {
+        Ok(url) => (true, format!("Jira URL: {}", url)),
+        Err(e) => (false, e.to_string()),
+    });
+    if profile.auth_scheme == AuthScheme::Basic {
+        checks.push(if profile.email.validate_email() {
+            (true, format!("Email: {}", profile.email))
+        } else {
+            (false, format!("Email is not valid: '{}'", profile.email))
+        });
+    }
+    if profile.auth_scheme == AuthScheme::BasicPassword && profile.username.is_empty() {
+        checks.push((false, "Username is empty".to_string()));
+    }
+    let has_token = match profile.auth_scheme {
+        AuthScheme::OAuth => profile
+            .oauth
+            .as_ref()
+            .is_some_and(|oauth| !oauth.access_token.is_empty()),
+        AuthScheme::BasicPassword => !profile.password.is_empty(),
+        _ => !profile.api_token.is_empty(),
+    };
+    checks.push(if has_token {
+        (true, format!("{} credentials are set", profile.auth_scheme))
+    } else if profile.auth_scheme == AuthScheme::OAuth {
+        (false, "No OAuth tokens, run 'fast-task login'".to_string())
+    } else if profile.auth_scheme == AuthScheme::BasicPassword {
+        (false, "Password is empty".to_string())
+    } else {
+        (false, "Api token is empty".to_string())
+    });
+    checks.push(if profile.projects.is_empty() {
+        (false, "No projects configured".to_string())
+    } else {
+        (
+            true,
+            format!("{} project(s) configured", profile.projects.len()),
+        )
+    });
+    checks.push(if matches!(profile.api_version, 2 | 3) {
+        (true, format!("Api version: {}", profile.api_version))
+    } else {
+        (
+            false,
+            format!("Api version must be 2 or 3, got {}", profile.api_version),
+        )
+    });
+
+    say!("Profile: {}", profile_name);
+    for (passed, message) in &checks {
+        if *passed {
+            say!("✅ {}", message);
+        } else {
+            say!("❌ {}", message);
+        }
+    }
+    if checks.iter().all(|(passed, _)| *passed) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Opens the config file in the user's editor, seeding a template when it doesn't exist
+/// yet. A file that doesn't load is reported and can be edited again right away
+fn edit_config(config_path: &Path, profile: Option<&str>) -> ExitCode {
+    let editor = ["VISUAL", "EDITOR"]
+        .into_iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+
+    if !config_path.exists() {
+        let created = config_path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(config_path, config_template()));
+        if let Err(err) = created {
+            say!("❌ Could not create {}: {}", config_path.display(), err);
+            return ExitCode::FAILURE;
+        }
+        say!("📝 Created {} from a template", config_path.display());
+    }
+
+    loop {
+        match run_editor(&editor, config_path) {
+            Ok(status) if status.success() => {}
+            Ok(status) => {
+                say!("❌ {} exited with {}", editor, status);
+                return ExitCode::FAILURE;
+            }
+            Err(err) => {
+                say!("❌ Could not start {}: {}", editor, err);
+                say!("💡 Set EDITOR to the editor to use, e.g. EDITOR=nano");
+                return ExitCode::FAILURE;
+            }
+        }
+
+        let err = match load_config(config_path) {
+            Ok(config) => {
+                let profile_name = config.resolve_profile_name(profile);
+                let Some(profile) = config.profiles.get(&profile_name) else {
+                    say!("✅ Config saved, it has no profile '{}'", profile_name);
+                    return ExitCode::SUCCESS;
+                };
+                return match profile.clone().with_env_overrides() {
+                    Ok(profile) => validate_profile(&profile, &profile_name),
+                    Err(err) => {
+                        say!("❌ {}", err);
+                        ExitCode::FAILURE
+                    }
+                };
+            }
+            Err(err) => err,
+        };
+        say!("❌ {}", err);
+        let edit_again = io::stdin().is_terminal()
+            && Confirm::new("Open the editor again?")
+                .with_default(true)
+                .prompt()
+                .or_exit();
+        if !edit_again {
+            return ExitCode::FAILURE;
+        }
+    }
+}
+
+/// Runs the editor command on the file, it may carry arguments like `code --wait`
+fn run_editor(editor: &str, path: &Path) -> io::Result<process::ExitStatus> {
+    let mut command_parts = editor.split_whitespace();
+    let program = command_parts
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "empty editor command"))?;
+    process::Command::new(program)
+        .args(command_parts)
+        .arg(path)
+        .status()
+}
+
+/// Offers to run the config wizard and `add-project` when there is no config file yet.
+/// Returns the config the command should continue with
+async fn first_run_setup(
+    config: Config,
+    config_path: &Path,
+    profile_name: &str,
+    add_project: bool,
+) -> Result<Config, ExitCode> {
+    let set_up = Confirm::new("No configuration found — set one up now?")
+        .with_default(true)
+        .prompt()
+        .or_exit();
+    if !set_up {
+        return Ok(config);
+    }
+
+    let code = interactive_set_config(&config, config_path, profile_name);
+    if code != ExitCode::SUCCESS {
+        return Err(code);
+    }
+    let mut config = reload_config(config_path)?;
+
+    if add_project {
+        say!();
+        let code = interactive_add_project(&config, config_path, profile_name).await;
+        if code != ExitCode::SUCCESS {
+            return Err(code);
+        }
+        config = reload_config(config_path)?;
+    }
+    say!();
+    Ok(config)
+}
+
+fn reload_config(config_path: &Path) -> Result<Config, ExitCode> {
+    load_config(config_path).map_err(|err| {
+        say!("❌ Cannot read the saved config: {}", err);
+        ExitCode::FAILURE
+    })
+}
+
+fn interactive_set_config(
+    original_config: &Config,
+    config_path: &Path,
+    profile_name: &str,
+) -> ExitCode {
+    say!(
+        "🎯 Setup a jira configuration for profile '{}'\n",
+        profile_name
+    );
+
+    // Answering no at the review goes through the prompts again, pre-filled
+    let mut jira_url = String::new();
+    let mut auth_scheme = AuthScheme::Basic;
+    let mut email = String::new();
+    let mut api_token = String::new();
+    let mut username = String::new();
+    let mut password = String::new();
+    let mut api_version = 2;
+    let mut use_keyring = true;
+    let mut api_token_file;
+    loop {
+        loop {
+            jira_url = Text::new("Jira URL:")
+                .with_help_message("Enter your Jira instance URL (include https://)")
+                .with_placeholder("e.g., https://company.atlassian.net")
+                .with_initial_value(&jira_url)
+                .prompt()
+                .or_exit();
+
+            match normalize_base_url(&jira_url) {
+                Ok(normalized) => jira_url = normalized,
+                Err(e) => {
+                    say!("❌ {}. Try again", e);
+                    continue;
+                }
+            }
+            break;
+        }
+
+        let schemes = vec![
+            AuthScheme::Basic,
+            AuthScheme::Bearer,
+            AuthScheme::BasicPassword,
+        ];
+        let starting_cursor = schemes
+            .iter()
+            .position(|scheme| *scheme == auth_scheme)
+            .unwrap_or_default();
+        auth_scheme = Select::new("Authentication scheme:", schemes)
+            .with_help_message(
+                "Basic for Jira Cloud API tokens, Bearer for Data Center personal access tokens, BasicPassword for Data Center without tokens",
+            )
+            .with_starting_cursor(starting_cursor)
+            .prompt()
+            .or_exit();
+
+        api_token_file = original_config
+            .profiles
+            .get(profile_name)
+            .and_then(|profile| profile.api_token_file.clone())
+            .filter(|_| auth_scheme != AuthScheme::BasicPassword);
+        if auth_scheme == AuthScheme::BasicPassword {
+            say!(
+                "⚠️ A password grants everything your account can do and doesn't expire like a token."
+            );
+            say!("   Prefer Bearer with a personal access token if your Jira supports them");
+            loop {
+                username = Text::new("Your Jira username:")
+                    .with_help_message("The name you log in to Jira with, not the email")
+                    .with_initial_value(&username)
+                    .prompt()
+                    .or_exit();
+
+                if username.trim().is_empty() {
+                    say!("❌ Username cannot be empty. Try again");
+                    continue;
+                }
+                username = username.trim().to_string();
+                break;
+            }
+            password = prompt_secret("password", &password);
+        } else {
+            loop {
+                email = Text::new("Your Jira email:")
+                    .with_help_message("Enter your email address for Jira authentication")
+                    .with_placeholder("user@company.com")
+                    .with_initial_value(&email)
+                    .prompt()
+                    .or_exit();
+
+                if !email.validate_email() {
+                    say!("❌ Email is not valid. Try again");
+                    continue;
+                }
+                break;
+            }
+            if let Some(ref path) = api_token_file {
+                say!("ℹ️ Api token is read from {}", path.display());
+            } else {
+                api_token = prompt_secret("api token", &api_token);
+            }
+        }
+        api_version = Select::new("Jira REST API version:", vec![2, 3])
+            .with_help_message(
+                "Use 3 for newer Jira Cloud instances that expect rich text descriptions",
+            )
+            .with_starting_cursor(if api_version == 3 { 1 } else { 0 })
+            .prompt()
+            .or_exit();
+
+        let secret = if auth_scheme == AuthScheme::BasicPassword {
+            "password"
+        } else {
+            "api token"
+        };
+        use_keyring = api_token_file.is_none()
+            && Confirm::new(&format!("Store the {} in the OS keyring?", secret))
+                .with_default(use_keyring)
+                .with_help_message(&format!(
+                    "Otherwise the {} is saved in plain text in the config file",
+                    secret
+                ))
+                .prompt()
+                .or_exit();
+
+        say!("\n📋 Profile '{}':", profile_name);
+        say!("   URL: {}", jira_url);
+        say!("   Auth scheme: {}", auth_scheme);
+        if auth_scheme == AuthScheme::BasicPassword {
+            say!("   Username: {}", username);
+            say!("   Password: ****");
+        } else {
+            say!("   Email: {}", email);
+            match api_token_file {
+                Some(ref path) => say!("   Api token file: {}", path.display()),
+                None => say!("   Api token: {}", mask_token(&api_token)),
+            }
+        }
+        say!("   Api version: {}", api_version);
+        if api_token_file.is_none() {
+            say!(
+                "   Stored in: {}",
+                if use_keyring {
+                    "OS keyring"
+                } else {
+                    "config file"
+                }
+            );
+        }
+        let save = Confirm::new("Save this configuration?")
+            .with_default(true)
+            .with_help_message("Answer no to go through the answers again")
+            .prompt()
+            .or_exit();
+        if save {
+            break;
+        }
+        say!("🔁 Fix what needs fixing, Enter keeps an answer\n");
+    }
+
+    let mut config = original_config.clone();
+    if config.profiles.is_empty() {
+        config.default_profile = profile_name.to_string();
+    }
+    // Settings the wizard doesn't ask about are kept from the existing profile
+    let profile = config.profiles.entry(profile_name.to_string()).or_default();
+    profile.jira_url = jira_url;
+    if auth_scheme == AuthScheme::BasicPassword {
+        profile.username = username;
+        profile.password = password;
+        profile.api_token = String::new();
+    } else {
+        profile.email = email;
+        profile.api_token = api_token;
+        profile.password = String::new();
+    }
+    profile.auth_scheme = auth_scheme;
+    profile.use_keyring = use_keyring;
+    profile.api_version = api_version;
+    match save_config(config, config_path) {
+        Ok(_) => {
+            say!("Configuration saved!");
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            say!("Failed to save config: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Asks for a hidden value until one is entered. When the wizard goes through its
+/// prompts again, an empty answer keeps the value entered before
+fn prompt_secret(name: &str, previous: &str) -> String {
+    let help = if previous.is_empty() {
+        "Input is hidden".to_string()
+    } else {
+        format!(
+            "Input is hidden, leave empty to keep the {} entered before",
+            name
+        )
+    };
+    loop {
+        let value = Password::new(&format!("Your Jira {}:", name))
+            .with_help_message(&help)
+            .with_custom_confirmation_message(&format!("Repeat the {}:", name))
+            .with_custom_confirmation_error_message(&format!("The {}s don't match.", name))
+            .prompt()
+            .or_exit();
+
+        if !value.trim().is_empty() {
+            return value;
+        }
+        if !previous.is_empty() {
+            return previous.to_string();
+        }
+        say!("❌ The {} cannot be empty. Try again", name);
+    }
+}
+
+/// Saves a new api token only once Jira accepts it, the replaced one is kept as
+/// `previous_api_token` until the next rotation. `restore` swaps that backup back in
+async fn rotate_token(
+    original_config: &Config,
+    config_path: &Path,
+    profile_name: &str,
+    profile: &Profile,
+    restore: bool,
+) -> ExitCode {
+    let Some(saved) = original_config.profiles.get(profile_name) else {
+        say!("❌ Please configure Jira connection first:");
+        say!("fast-task config");
+        return ExitCode::FAILURE;
+    };
+    if saved.auth_scheme == AuthScheme::OAuth {
+        say!(
+            "❌ Profile '{}' uses OAuth, run 'fast-task login' to renew its tokens",
+            profile_name
+        );
+        return ExitCode::FAILURE;
+    }
+    if saved.auth_scheme == AuthScheme::BasicPassword {
+        say!(
+            "❌ Profile '{}' logs in with a password, run 'fast-task config' to change it",
+            profile_name
+        );
+        return ExitCode::FAILURE;
+    }
+    if let Some(ref path) = saved.api_token_file {
+        say!(
+            "❌ The api token is read from {}, replace it there",
+            path.display()
+        );
+        return ExitCode::FAILURE;
+    }
+    if profile.jira_url.is_empty() || profile.email.is_empty() {
+        say!("❌ Please configure Jira connection first:");
+        say!("fast-task config");
+        return ExitCode::FAILURE;
+    }
+
+    let api_token = if restore {
+        if saved.previous_api_token.is_empty() {
+            say!("❌ There is no previous token to restore");
+            return ExitCode::FAILURE;
+        }
+        saved.previous_api_token.clone()
+    } else {
+        prompt_new_token()
+    };
+    if api_token == saved.api_token {
+        say!("❌ This is the token already in use");
+        return ExitCode::FAILURE;
+    }
+
+    let candidate = Profile {
+        api_token: api_token.clone(),
+        ..profile.clone()
+    };
+    let Some(client) = connect(original_config, &candidate, config_path, profile_name) else {
+        return ExitCode::FAILURE;
+    };
+    let label = if restore { "previous" } else { "new" };
+    say!("🔍 Verifying the {} token...", label);
+    let user = match client.get_myself().await {
+        Ok(user) => user,
+        Err(e) => {
+            say!("❌ The {} token doesn't work: {}", label, e);
+            say!("💡 The saved token was left unchanged");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut config = original_config.clone();
+    let rotated = config.profiles.entry(profile_name.to_string()).or_default();
+    rotated.previous_api_token = std::mem::replace(&mut rotated.api_token, api_token);
+    match save_config(config, config_path) {
+        Ok(_) => {
+            if restore {
+                say!(
+                    "✅ Previous token restored, authenticated as {}",
+                    user.display_name
+                );
+                say!("   The replaced token is kept as a backup until the next rotation");
+            } else {
+                say!("✅ Token rotated, authenticated as {}", user.display_name);
+                say!("   The previous token is kept as a backup until the next rotation");
+            }
+            if env::var_os(API_TOKEN_ENV).is_some() {
+                say!(
+                    "⚠️ {} is set and still overrides the saved token",
+                    API_TOKEN_ENV
+                );
+            }
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            say!("Failed to save config: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn prompt_new_token() -> String {
+    loop {
+        let api_token = Password::new("New Jira api token:")
+            .with_help_message("Input is hidden, the token is verified before it is saved")
+            .without_confirmation()
+            .prompt()
+            .or_exit();
+        if api_token.trim().is_empty() {
+            say!("❌ Api token cannot be empty. Try again");
+            continue;
+        }
+        break api_token.trim().to_string();
+    }
+}
+
+/// Runs the OAuth 2.0 authorization code flow and stores the tokens in the profile
+async fn login(
+    original_config: &Config,
+    config_path: &Path,
+    profile_name: &str,
+    profile: &Profile,
+    client_id: Option<String>,
+    client_secret: Option<String>,
+    port: u16,
+) -> ExitCode {
+    let client_id = client_id.unwrap_or_else(|| {
+        Text::new("OAuth client id:")
+            .with_help_message("From your OAuth 2.0 (3LO) app at developer.atlassian.com")
+            .prompt()
+            .or_exit()
+    });
+    let client_secret = client_secret.unwrap_or_else(|| {
+        Password::new("OAuth client secret:")
+            .without_confirmation()
+            .prompt()
+            .or_exit()
+    });
+
+    let http = match JiraClient::new(profile) {
+        Ok(client) => client.http_client().clone(),
+        Err(e) => {
+            say!("❌ {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let listener = match TcpListener::bind(("127.0.0.1", port)).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            say!(
+                "❌ Cannot listen on port {}: {}. Pass another one with --port",
+                port,
+                e
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let redirect_uri = oauth::redirect_uri(port);
+    let state = match oauth::random_state() {
+        Ok(state) => state,
+        Err(e) => {
+            say!("❌ Login failed: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let authorize_url = oauth::authorize_url(&client_id, &redirect_uri, &state);
+    say!("🔐 Authorize fast-task in the browser:");
+    say!("   {}", authorize_url);
+    if let Err(e) = open::that(authorize_url.as_str()) {
+        say!("⚠️ Could not open a browser ({}), open the link above", e);
+    }
+
+    let result = match oauth::wait_for_code(listener, &state).await {
+        Ok(code) => {
+            oauth::exchange_code(&http, &client_id, &client_secret, &code, &redirect_uri).await
+        }
+        Err(e) => Err(e),
+    };
+    let mut tokens = match result {
+        Ok(tokens) => tokens,
+        Err(e) => {
+            say!("❌ Login failed: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let resources = match oauth::accessible_resources(&http, &tokens.access_token).await {
+        Ok(resources) if !resources.is_empty() => resources,
+        Ok(_) => {
+            say!("❌ The app was not granted access to any Jira site");
+            return ExitCode::FAILURE;
+        }
+        Err(e) => {
+            say!("❌ Login failed: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let configured_site = profile.jira_url.trim_end_matches('/');
+    let resource = match resources
+        .iter()
+        .position(|resource| resource.url.trim_end_matches('/') == configured_site)
+    {
+        Some(index) => resources[index].clone(),
+        None if resources.len() == 1 => resources[0].clone(),
+        None => {
+            let options: Vec<String> = resources
+                .iter()
+                .map(|resource| format!("{} ({})", resource.name, resource.url))
+                .collect();
+            let selected = Select::new("Jira site:", options).raw_prompt().or_exit();
+            resources[selected.index].clone()
+        }
+    };
+    tokens.cloud_id = resource.id;
+
+    let mut config = original_config.clone();
+    if config.profiles.is_empty() {
+        config.default_profile = profile_name.to_string();
+    }
+    let logged_in = config.profiles.entry(profile_name.to_string()).or_default();
+    logged_in.jira_url = resource.url;
+    logged_in.auth_scheme = AuthScheme::OAuth;
+    logged_in.oauth = Some(tokens);
+
+    match JiraClient::new(logged_in) {
+        Ok(client) => match client.get_myself().await {
+            Ok(user) => {
+                say!("✅ Logged in as {}", user.display_name);
+                if let Some(email) = user.email_address {
+                    logged_in.email = email;
+                }
+            }
+            Err(e) => say!("⚠️ Logged in, but fetching the user failed: {}", e),
+        },
+        Err(e) => say!("⚠️ Logged in, but fetching the user failed: {}", e),
+    }
+
+    match save_config(config, config_path) {
+        Ok(_) => {
+            say!("Configuration saved!");
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            say!("Failed to save config: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn interactive_add_project(
+    original_config: &Config,
+    config_path: &Path,
+    profile_name: &str,
+) -> ExitCode {
+    // Answering no at the review goes through the prompts again, pre-filled
+    let mut project_key = String::new();
+    let mut project_name = String::new();
+    let mut default_issue_type: Option<String> = None;
+    loop {
+        loop {
+            project_key = Text::new("Your project key:")
+                .with_help_message("Enter your project key")
+                .with_placeholder("e.g. PRKEY")
+                .with_initial_value(&project_key)
+                .prompt()
+                .or_exit();
+
+            if project_key.trim().is_empty() {
+                say!("❌ Project key cannot be empty. Try again");
+                continue;
+            }
+            break;
+        }
+
+        // Verify the key against Jira when the connection is already configured
+        let mut jira_project_name = None;
+        let mut verified_profile = None;
+        if let Some(profile) = original_config
+            .profiles
+            .get(profile_name)
+            .filter(|profile| profile.is_configured())
+        {
+            say!("🔍 Checking project {} in Jira...", project_key);
+            let result = match JiraClient::new(profile) {
+                Ok(client) => client.get_project(&project_key).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(project) => {
+                    say!("✅ Found project: {}", project.name);
+                    jira_project_name = Some(project.name);
+                    verified_profile = Some(profile);
+                }
+                Err(JiraClientError::Response(StatusCode::NOT_FOUND, _)) => {
+                    say!("⚠️ Project {} was not found in Jira", project_key);
+                    let add_anyway = Confirm::new("Add it anyway?")
+                        .with_default(false)
+                        .prompt()
+                        .or_exit();
+                    if !add_anyway {
+                        return ExitCode::SUCCESS;
+                    }
+                }
+                Err(e) => {
+                    say!("⚠️ Could not verify project: {}", e);
+                }
+            }
+        }
+
+        loop {
+            let mut prompt = Text::new("Your Jira project name:")
+                .with_help_message("Enter name of your project (for display)");
+            if !project_name.is_empty() {
+                prompt = prompt.with_initial_value(&project_name);
+            } else if let Some(ref name) = jira_project_name {
+                prompt = prompt.with_default(name);
+            }
+            project_name = prompt.prompt().or_exit();
+
+            if project_name.trim().is_empty() {
+                say!("❌ Project name cannot be empty. Try again");
+                continue;
+            }
+            break;
+        }
+        default_issue_type = match verified_profile {
+            Some(profile) => {
+                prompt_default_issue_type(profile, &project_key, default_issue_type.as_deref())
+                    .await
+            }
+            None => None,
+        };
+
+        say!("\n📋 Project {}:", project_key);
+        say!("   Name: {}", project_name);
+        say!(
+            "   Default issue type: {}",
+            default_issue_type.as_deref().unwrap_or("-")
+        );
+        let save = Confirm::new("Save this project?")
+            .with_default(true)
+            .with_help_message("Answer no to go through the answers again")
+            .prompt()
+            .or_exit();
+        if save {
+            break;
+        }
+        say!("🔁 Fix what needs fixing, Enter keeps an answer\n");
+    }
+
+    let mut config = original_config.clone();
+    config
+        .profiles
+        .entry(profile_name.to_string())
+        .or_default()
+        .projects
+        .insert(
+            project_key,
+            ProjectConfig {
+                name: project_name,
+                default_issue_type,
+                board_id: None,
+                default_labels: Vec::new(),
+                default_assignee: None,
+            },
+        );
+    match save_config(config, config_path) {
+        Ok(_) => {
+            say!("Configuration saved!");
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            say!("Failed to save config: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Stores the project of a created issue so the next `create` pre-selects it
+fn remember_last_project(config: &Config, config_path: &Path, profile_name: &str, issue_key: &str) {
+    // Nothing to update when the connection only comes from environment variables
+    if !config.profiles.contains_key(profile_name) {
+        return;
+    }
+    let Some((project, _)) = issue_key.rsplit_once('-') else {
+        return;
+    };
+    if config.last_project.as_deref() == Some(project) {
+        return;
+    }
+
+    let mut config = config.clone();
+    config.last_project = Some(project.to_string());
+    if let Err(err) = save_config(config, config_path) {
+        say!("⚠️ Could not remember the last project: {}", err);
+    }
+}
+
+fn remember_idempotency_key(store: &IdempotencyStore, args: &CreateArgs, issue: &CreatedIssue) {
+    let Some(ref key) = args.idempotency_key else {
+        return;
+    };
+    if let Err(err) = store.put(key, issue) {
+        esay!("⚠️ Could not store the idempotency key: {}", err);
+    }
+}
+
+fn record_history(config_path: &Path, issue: &CreatedIssue) {
+    let project = issue
+        .key
+        .rsplit_once('-')
+        .map_or(issue.key.as_str(), |(project, _)| project);
+    let entry = HistoryEntry::new(project, &issue.key, &issue.summary);
+    if let Err(err) = append_history(config_path, &entry) {
+        // stderr keeps `--json` output parseable
+        esay!("⚠️ Could not write the history log: {}", err);
+    }
+}
+
+/// `current` is pre-selected, it's the answer given before when the wizard goes
+/// through its prompts again
+async fn prompt_default_issue_type(
+    profile: &Profile,
+    project_key: &str,
+    current: Option<&str>,
+) -> Option<String> {
+    let result = match JiraClient::new(profile) {
+        Ok(client) => client.get_project_issue_types(project_key).await,
+        Err(e) => Err(e),
+    };
+    let issue_types = match result {
+        Ok(issue_types) if !issue_types.is_empty() => issue_types,
+        Ok(_) => return None,
+        Err(e) => {
+            say!("⚠️ Could not fetch issue types: {}", e);
+            return None;
+        }
+    };
+
+    let mut options = vec!["No default".to_string()];
+    options.extend(issue_types.into_iter().map(|it| it.name));
+    let starting_cursor = current
+        .and_then(|current| options.iter().position(|option| option == current))
+        .unwrap_or_default();
+    let selected = Select::new("Default issue type:", options)
+        .with_help_message("Pre-selected when creating issues in this project")
+        .with_starting_cursor(starting_cursor)
+        .prompt()
+        .or_exit();
+    if selected == "No default" {
+        None
+    } else {
+        Some(selected)
+    }
+}
+
+async fn sync_projects(
+    original_config: &Config,
+    config_path: &Path,
+    profile_name: &str,
+    profile: &Profile,
+    filter: Option<&str>,
+    replace: bool,
+) -> ExitCode {
+    if !profile.is_configured() {
+        say!("❌ Please configure Jira connection first:");
+        say!("fast-task config");
+        return ExitCode::FAILURE;
+    }
+
+    let Some(client) = connect(original_config, profile, config_path, profile_name) else {
+        return ExitCode::FAILURE;
+    };
+    say!("🔍 Fetching projects from Jira...");
+    let mut fetched = match client.list_all_projects().await {
+        Ok(projects) => projects,
+        Err(e) => {
+            say!("❌ Failed to fetch projects: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Some(filter) = filter {
+        let filter = filter.to_lowercase();
+        fetched.retain(|project| {
+            project.key.to_lowercase().contains(&filter)
+                || project.name.to_lowercase().contains(&filter)
+        });
+    }
+
+    let mut config = original_config.clone();
+    let projects = &mut config
+        .profiles
+        .entry(profile_name.to_string())
+        .or_default()
+        .projects;
+    let mut added = 0;
+    let mut removed = 0;
+    if replace {
+        let before = projects.len();
+        projects.retain(|key, _| fetched.iter().any(|project| &project.key == key));
+        removed = before - projects.len();
+    }
+    // Settings like default_issue_type of already configured projects are kept
+    for project in fetched {
+        if let Entry::Vacant(entry) = projects.entry(project.key) {
+            say!("  + {} - {}", entry.key(), project.name);
+            entry.insert(ProjectConfig {
+                name: project.name,
+                default_issue_type: None,
+                board_id: None,
+                default_labels: Vec::new(),
+                default_assignee: None,
+            });
+            added += 1;
+        }
+    }
+
+    match save_config(config, config_path) {
+        Ok(_) => {
+            say!("✅ Projects synced: {} added, {} removed", added, removed);
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            say!("Failed to save config: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn remove_project(
+    original_config: &Config,
+    config_path: &Path,
+    profile_name: &str,
+    key: Option<String>,
+) -> ExitCode {
+    let projects = original_config
+        .profiles
+        .get(profile_name)
+        .map(|profile| profile.projects.clone())
+        .unwrap_or_default();
+
+    let project_key = match key {
+        Some(key) => key,
+        None => {
+            if projects.is_empty() {
+                say!("No projects configured. Nothing to remove.");
+                return ExitCode::SUCCESS;
+            }
+            let project_options: Vec<String> = projects.keys().cloned().collect();
+            let project_key = Select::new("Which project to remove?", project_options)
+                .prompt()
+                .or_exit();
+
+            let confirm = Confirm::new(&format!("Remove project {}?", project_key))
+                .with_default(false)
+                .prompt()
+                .or_exit();
+            if !confirm {
+                say!("Nothing removed");
+                return ExitCode::SUCCESS;
+            }
+            project_key
+        }
+    };
+
+    if !projects.contains_key(&project_key) {
+        say!("❌ Project '{}' is not configured", project_key);
+        return ExitCode::FAILURE;
+    }
+
+    let mut config = original_config.clone();
+    if let Some(profile) = config.profiles.get_mut(profile_name) {
+        profile.projects.remove(&project_key);
+    }
+    match save_config(config, config_path) {
+        Ok(_) => {
+            say!("Project {} removed", project_key);
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            say!("Failed to save config: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Prints the boards and, with `assign`, stores one as the project's board_id.
+/// The board must be in the list, so a typo can't break sprint selection later
+async fn list_boards(
+    client: &JiraClient,
+    original_config: &Config,
+    config_path: &Path,
+    profile_name: &str,
+    project: Option<&str>,
+    assign: Option<u64>,
+) -> ExitCode {
+    let boards = match client.list_boards(project).await {
+        Ok(boards) => boards,
+        Err(e) => {
+            say!("❌ Failed to fetch boards: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if boards.is_empty() {
+        match project {
+            Some(project) => say!("No boards found for project {}", project),
+            None => say!("No boards found"),
+        }
+    } else {
+        let id_width = boards
+            .iter()
+            .map(|board| board.id.to_string().len())
+            .max()
+            .unwrap_or_default();
+        for board in &boards {
+            say!(
+                "  {:>width$}  {} ({})",
+                board.id,
+                board.name,
+                board.board_type,
+                width = id_width
+            );
+        }
+    }
+
+    let (Some(board_id), Some(project)) = (assign, project) else {
+        return ExitCode::SUCCESS;
+    };
+    let Some(board) = boards.iter().find(|board| board.id == board_id) else {
+        say!("❌ Board {} doesn't show project {}", board_id, project);
+        return ExitCode::FAILURE;
+    };
+    if board.board_type != "scrum" {
+        say!(
+            "⚠️ {} is a {} board, only scrum boards have sprints",
+            board.name,
+            board.board_type
+        );
+    }
+
+    let mut config = original_config.clone();
+    let Some(project_config) = config
+        .profiles
+        .get_mut(profile_name)
+        .and_then(|profile| profile.projects.get_mut(project))
+    else {
+        say!("❌ Project '{}' is not configured", project);
+        say!("fast-task list-projects");
+        return ExitCode::FAILURE;
+    };
+    project_config.board_id = Some(board_id);
+
+    match save_config(config, config_path) {
+        Ok(_) => {
+            say!(
+                "✅ Sprints for {} are now taken from board {}",
+                project,
+                board.name
+            );
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            say!("Failed to save config: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn rename_project(
+    original_config: &Config,
+    config_path: &Path,
+    profile_name: &str,
+    key: &str,
+    name: Option<String>,
+) -> ExitCode {
+    let mut config = original_config.clone();
+    let Some(project) = config
+        .profiles
+        .get_mut(profile_name)
+        .and_then(|profile| profile.projects.get_mut(key))
+    else {
+        say!("❌ Project '{}' is not configured", key);
+        say!("fast-task list-projects");
+        return ExitCode::FAILURE;
+    };
+
+    let name = match name {
+        Some(name) => name,
+        None => Text::new("New project name:")
+            .with_initial_value(&project.name)
+            .prompt()
+            .or_exit(),
+    };
+    if name.trim().is_empty() {
+        say!("❌ Project name cannot be empty");
+        return ExitCode::FAILURE;
+    }
+    let old_name = std::mem::replace(&mut project.name, name.trim().to_string());
+
+    match save_config(config, config_path) {
+        Ok(_) => {
+            say!("Project {} renamed: {} → {}", key, old_name, name.trim());
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            say!("Failed to save config: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn transition_issue(client: &JiraClient, key: &str, to: Option<&str>) -> ExitCode {
+    let transitions = match client.get_transitions(key).await {
+        Ok(transitions) if transitions.is_empty() => {
+            say!("❌ No transitions available for {}", key);
+            return ExitCode::FAILURE;
+        }
+        Ok(transitions) => transitions,
+        Err(e) => {
+            say!("❌ Failed to fetch transitions for {}: {}", key, e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let transition = match to {
+        Some(to) => match transitions
+            .iter()
+            .find(|transition| transition.name.eq_ignore_ascii_case(to))
+        {
+            Some(transition) => transition,
+            None => {
+                let names: Vec<&str> = transitions.iter().map(|t| t.name.as_str()).collect();
+                say!("❌ Transition '{}' not available for {}", to, key);
+                say!("💡 Available transitions: {}", names.join(", "));
+                return ExitCode::FAILURE;
+            }
+        },
+        None => {
+            let transition_options: Vec<String> =
+                transitions.iter().map(|t| t.name.clone()).collect();
+            let selected_option = Select::new("Transition to:", transition_options.clone())
+                .with_help_message("Select the workflow transition to apply")
+                .prompt()
+                .or_exit();
+            match transition_options
+                .iter()
+                .position(|option| option == &selected_option)
+            {
+                Some(index) => &transitions[index],
+                None => return ExitCode::FAILURE,
+            }
+        }
+    };
+
+    match client.transition_issue(key, &transition.id).await {
+        Ok(_) => {
+            say!("✅ {} moved via '{}'", key, transition.name);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            say!("❌ Failed to transition {}: {}", key, e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// How long `create --wait` polls for the created issue
+const WAIT_TIMEOUT: Duration = Duration::from_secs(30);
+const WAIT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Polls the created issue until Jira returns it, returns false when it gave up.
+/// Only 404s are retried, warnings go to stderr to keep `--json` output parseable
+async fn wait_for_issue(client: &JiraClient, key: &str) -> bool {
+    let deadline = Instant::now() + WAIT_TIMEOUT;
+    loop {
+        match client.get_issue(key, &["summary"]).await {
+            Ok(_) => return true,
+            Err(JiraClientError::Response(StatusCode::NOT_FOUND, _))
+                if Instant::now() < deadline =>
+            {
+                tokio::time::sleep(WAIT_POLL_INTERVAL).await;
+            }
+            Err(JiraClientError::Response(StatusCode::NOT_FOUND, _)) => {
+                esay!(
+                    "⚠️ {} is still not available after {}s, going on anyway",
+                    key,
+                    WAIT_TIMEOUT.as_secs()
+                );
+                return false;
+            }
+            Err(e) => {
+                esay!("⚠️ Could not check that {} is available: {}", key, e);
+                return false;
+            }
+        }
+    }
+}
+
+/// Moves a freshly created issue to the profile's start_status and returns the transition
+/// used. The issue exists either way, so problems are only warnings, on stderr to keep
+/// `--json` output parseable
+async fn start_issue(client: &JiraClient, key: &str) -> Option<String> {
+    let status = &client.profile().start_status;
+    let transitions = match client.get_transitions(key).await {
+        Ok(transitions) => transitions,
+        Err(e) => {
+            esay!("⚠️ Could not fetch transitions for {}: {}", key, e);
+            return None;
+        }
+    };
+
+    let Some(transition) = transitions.iter().find(|transition| {
+        transition.name.eq_ignore_ascii_case(status)
+            || transition
+                .to
+                .as_ref()
+                .is_some_and(|to| to.name.eq_ignore_ascii_case(status))
+    }) else {
+        let names: Vec<&str> = transitions.iter().map(|t| t.name.as_str()).collect();
+        esay!(
+            "⚠️ {}'s workflow has no transition to '{}', the issue was left as created",
+            key,
+            status
+        );
+        if !names.is_empty() {
+            esay!("💡 Available transitions: {}", names.join(", "));
+        }
+        return None;
+    };
+
+    match client.transition_issue(key, &transition.id).await {
+        Ok(_) => Some(transition.name.clone()),
+        Err(e) => {
+            esay!("⚠️ Could not move {} to '{}': {}", key, status, e);
+            None
+        }
+    }
+}
+
+async fn link_issues(
+    client: &JiraClient,
+    from: &str,
+    to: &str,
+    link_type: Option<&str>,
+) -> ExitCode {
+    for key in [from, to] {
+        match client.get_issue(key, &["summary"]).await {
+            Ok(_) => {}
+            Err(JiraClientError::Response(StatusCode::NOT_FOUND, _)) => {
+                say!("❌ Issue {} was not found", key);
+                return ExitCode::FAILURE;
+            }
+            Err(e) => {
+                say!("❌ Failed to check issue {}: {}", key, e);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let link_types = match client.get_issue_link_types().await {
+        Ok(link_types) if link_types.is_empty() => {
+            say!("❌ No issue link types are configured in Jira");
+            return ExitCode::FAILURE;
+        }
+        Ok(link_types) => link_types,
+        Err(e) => {
+            say!("❌ Failed to fetch issue link types: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let link_type = match link_type {
+        Some(name) => match link_types
+            .iter()
+            .find(|link_type| link_type.name.eq_ignore_ascii_case(name))
+        {
+            Some(link_type) => link_type,
+            None => {
+                let names: Vec<&str> = link_types.iter().map(|t| t.name.as_str()).collect();
+                say!("❌ Link type '{}' not found", name);
+                say!("💡 Available link types: {}", names.join(", "));
+                return ExitCode::FAILURE;
+            }
+        },
+        None => {
+            let link_options: Vec<String> = link_types
+                .iter()
+                .map(|t| format!("{} ({} {} {})", t.name, from, t.outward, to))
+                .collect();
+            let selected_option = Select::new("Link type:", link_options.clone())
+                .prompt()
+                .or_exit();
+            match link_options
+                .iter()
+                .position(|option| option == &selected_option)
+            {
+                Some(index) => &link_types[index],
+                None => return ExitCode::FAILURE,
+            }
+        }
+    };
+
+    match client.create_issue_link(&link_type.name, from, to).await {
+        Ok(_) => {
+            say!("✅ {} {} {}", from, link_type.outward, to);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            say!("❌ Failed to link {} and {}: {}", from, to, e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Uploads every readable file, one failure doesn't stop the rest of the batch
+async fn attach_files(client: &JiraClient, key: &str, files: &[PathBuf]) -> ExitCode {
+    let mut failed = 0;
+
+    for file in files {
+        if let Err(e) = fs::File::open(file).and_then(|f| f.metadata()) {
+            say!("❌ {}: {}", file.display(), e);
+            failed += 1;
+            continue;
+        }
+        if !file.is_file() {
+            say!("❌ {}: not a regular file", file.display());
+            failed += 1;
+            continue;
+        }
+
+        match client.add_attachment(key, file).await {
+            Ok(_) => say!("✅ {} attached", file.display()),
+            Err(e) => {
+                say!("❌ {}: {}", file.display(), e);
+                failed += 1;
+            }
+        }
+    }
+
+    say!(
+        "📎 {} of {} file(s) attached to {}",
+        files.len() - failed,
+        files.len(),
+        key
+    );
+    if failed == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Reads a comment line by line until an empty line is entered
+fn prompt_multiline_comment() -> String {
+    say!("✍️ Enter the comment, finish with an empty line");
+    let mut lines = Vec::new();
+    loop {
+        let line = Text::new(">").prompt().or_exit();
+        if line.is_empty() {
+            break;
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+fn parse_field(value: &str) -> Result<(String, Value), String> {
+    let (name, raw_value) = value
+        .split_once('=')
+        .ok_or_else(|| format!("'{}' is not a key=value pair", value))?;
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(format!("'{}' is missing the field key", value));
+    }
+    let value = serde_json::from_str(raw_value).unwrap_or_else(|_| json!(raw_value));
+    Ok((name.to_string(), value))
+}
+
+/// Quotes a value for JQL, escaping the backslashes and quotes that would end the
+/// string early or let the value add clauses of its own
+fn jql_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn parse_jql(value: &str) -> Result<String, String> {
+    if value.trim().is_empty() {
+        return Err("JQL cannot be empty".to_string());
+    }
+    Ok(value.to_string())
+}
+
+/// Accepts Jira relative periods like `30m`, `12h`, `7d` or `2w`
+fn parse_since(value: &str) -> Result<String, String> {
+    let value = value.trim();
+    let unit_start = value.char_indices().last().map_or(0, |(index, _)| index);
+    let (amount, unit) = value.split_at(unit_start);
+    if amount.is_empty()
+        || !amount.chars().all(|c| c.is_ascii_digit())
+        || !matches!(unit, "m" | "h" | "d" | "w")
+    {
+        return Err(format!(
+            "'{}' is not a period, use a number followed by m, h, d or w",
+            value
+        ));
+    }
+    Ok(value.to_string())
+}
+
+/// Jira durations are whitespace separated amounts with a w, d, h or m unit, e.g. `1w 2d 4h`.
+/// Checked here because Jira's own error for `3 hours` doesn't name the field
+fn parse_estimate(value: &str) -> Result<String, String> {
+    let invalid = || {
+        format!(
+            "'{}' is not a Jira duration, use amounts with w, d, h or m, e.g. 3h or 1w 2d",
+            value
+        )
+    };
+
+    let parts: Vec<String> = value
+        .split_whitespace()
+        .map(|part| part.to_lowercase())
+        .collect();
+    if parts.is_empty() {
+        return Err(invalid());
+    }
+    for part in &parts {
+        let Some(amount) = part.strip_suffix(['w', 'd', 'h', 'm']) else {
+            return Err(invalid());
+        };
+        if amount.is_empty() || !amount.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+    }
+    Ok(parts.join(" "))
+}
+
+/// Accepts `YYYY-MM-DD`, `today`, `tomorrow`, `+Nd`, `+Nw` and weekdays such as
+/// `friday` or `next friday`, which mean the next such day after today
+fn parse_due(value: &str) -> Result<NaiveDate, String> {
+    let value = value.trim().to_lowercase();
+    let today = Local::now().date_naive();
+    let invalid = || {
+        format!(
+            "'{}' is not a date, use YYYY-MM-DD, today, tomorrow, +3d, +2w or next friday",
+            value
+        )
+    };
+
+    if let Ok(date) = NaiveDate::parse_from_str(&value, "%Y-%m-%d") {
+        return Ok(date);
+    }
+    match value.as_str() {
+        "today" => return Ok(today),
+        "tomorrow" => return Ok(today + Days::new(1)),
+        _ => {}
+    }
+    if let Some(period) = value.strip_prefix('+') {
+        let unit_start = period.char_indices().last().map_or(0, |(index, _)| index);
+        let (amount, unit) = period.split_at(unit_start);
+        let amount: u64 = amount.parse().map_err(|_| invalid())?;
+        let days = match unit {
+            "d" => amount,
+            "w" => amount.checked_mul(7).ok_or_else(invalid)?,
+            _ => return Err(invalid()),
+        };
+        return today.checked_add_days(Days::new(days)).ok_or_else(invalid);
+    }
+
+    let weekday: Weekday = value
+        .strip_prefix("next ")
+        .unwrap_or(&value)
+        .trim()
+        .parse()
+        .map_err(|_| invalid())?;
+    let days_ahead =
+        (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+    Ok(today
+        + Days::new(if days_ahead == 0 {
+            7
+        } else {
+            days_ahead.into()
+        }))
+}
+
+/// Shortens text to at most `max_chars` characters, ending with an ellipsis when cut.
+/// Counts characters rather than bytes so multibyte text is never split
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_chars.saturating_sub(3)).collect();
+    truncated.push_str("...");
+    truncated
+}
+
+fn print_issue_card(issue: &IssueDetails, issue_url: &str) {
+    say!("{} {}", issue.key, issue.summary);
+    say!("   Type: {}", issue.issue_type);
+    say!("   Status: {}", issue.status);
+    say!(
+        "   Assignee: {}",
+        issue.assignee.as_deref().unwrap_or("Unassigned")
+    );
+    say!("   Reporter: {}", issue.reporter.as_deref().unwrap_or("-"));
+    say!("🔗 {}", issue_url);
+    if let Some(ref description) = issue.description
+        && !description.trim().is_empty()
+    {
+        say!();
+        for line in description.lines() {
+            say!("   {}", line);
+        }
+    }
+}
+
+fn print_history(entries: &[HistoryEntry]) {
+    let key_width = entries
+        .iter()
+        .map(|entry| entry.key.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("KEY".len());
+
+    say!("{:16}  {:key_width$}  TITLE", "CREATED", "KEY");
+    for entry in entries {
+        let created_at = DateTime::parse_from_rfc3339(&entry.created_at)
+            .map(|created_at| created_at.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|_| entry.created_at.clone());
+        say!(
+            "{:16}  {:key_width$}  {}",
+            created_at,
+            entry.key,
+            entry.title
+        );
+    }
+}
+
+/// Default of `bulk-create --concurrency`, low enough to stay clear of rate limits
+const DEFAULT_BULK_CONCURRENCY: u8 = 3;
+
+/// One line of a `bulk-create` file
+#[derive(Deserialize)]
+struct BulkIssue {
+    title: String,
+    /// Key or name, defaults to `--project`
+    #[serde(default)]
+    project: Option<String>,
+    #[serde(default, rename = "type")]
+    issue_type: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    priority: Option<String>,
+    #[serde(default)]
+    labels: Vec<String>,
+    #[serde(default)]
+    assignee: Option<String>,
+}
+
+/// A `bulk-create` line resolved against Jira, ready to be sent
+struct PreparedIssue {
+    line: usize,
+    project: String,
+    issue: BulkIssue,
+    issue_type_id: String,
+    priority_id: Option<String>,
+    labels: Vec<String>,
+}
+
+/// Issues of the file with their line numbers, or one message per line that doesn't parse
+fn read_bulk_file(path: &Path) -> Result<Vec<(usize, BulkIssue)>, Vec<String>> {
+    let content = if path.as_os_str() == "-" {
+        let mut content = String::new();
+        io::stdin().read_to_string(&mut content).map(|_| content)
+    } else {
+        fs::read_to_string(path)
+    }
+    .map_err(|err| vec![format!("Cannot read '{}': {}", path.display(), err)])?;
+
+    let mut issues = Vec::new();
+    let mut problems = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<BulkIssue>(line) {
+            Ok(issue) => issues.push((index + 1, issue)),
+            Err(err) => problems.push(format!("line {}: {}", index + 1, err)),
+        }
+    }
+    if problems.is_empty() {
+        Ok(issues)
+    } else {
+        Err(problems)
+    }
+}
+
+fn prepare_bulk_issue(
+    profile: &Profile,
+    project: &str,
+    issue_types: &[IssueType],
+    priorities: &[Priority],
+    issue_type: &str,
+    no_default_labels: bool,
+    issue: &BulkIssue,
+) -> Result<(String, Option<String>, Vec<String>), IssueCreateError> {
+    if issue.title.trim().is_empty() {
+        return Err(IssueCreateError::EmptyTitle);
+    }
+    if !is_single_line(&issue.title) {
+        return Err(IssueCreateError::InvalidSummary);
+    }
+
+    let selected_issue_type = find_issue_type(issue_types, issue_type).ok_or_else(|| {
+        IssueCreateError::UnknownIssueType(issue_type.to_string(), project.to_string())
+    })?;
+    if selected_issue_type.subtask {
+        return Err(IssueCreateError::MissingParent(
+            selected_issue_type.name.clone(),
+        ));
+    }
+
+    let priority_id = match issue.priority {
+        Some(ref name_or_id) => Some(
+            priorities
+                .iter()
+                .find(|p| p.id == *name_or_id || p.name.eq_ignore_ascii_case(name_or_id))
+                .ok_or_else(|| IssueCreateError::UnknownPriority(name_or_id.clone()))?
+                .id
+                .clone(),
+        ),
+        None => None,
+    };
+    let labels = with_default_labels(
+        profile,
+        project,
+        validate_labels(&issue.labels)?,
+        no_default_labels,
+    )?;
+    Ok((selected_issue_type.id.clone(), priority_id, labels))
+}
+
+/// `me` and `@me` stand for the current user, like `currentUser()` in JQL
+fn is_me(user: &str) -> bool {
+    let user = user.trim();
+    user.eq_ignore_ascii_case("me") || user.eq_ignore_ascii_case("@me")
+}
+
+/// Replaces `me` with the account id (Jira Cloud) or username (Data Center) of the
+/// user the credentials belong to
+async fn resolve_me(client: &JiraClient, user: &str) -> Result<String, JiraClientError> {
+    if !is_me(user) {
+        return Ok(user.to_string());
+    }
+    client.get_myself().await.map(|me| me.id().to_string())
+}
+
+/// Project a `bulk-create` line goes to, its own "project" wins over `--project`
+fn bulk_project(
+    profile: &Profile,
+    row_project: Option<&str>,
+    default_project: Option<&str>,
+    allow_unknown_projects: bool,
+) -> Result<String, String> {
+    let Some(project) = row_project else {
+        return default_project
+            .map(str::to_string)
+            .ok_or_else(|| "no \"project\", pass --project".to_string());
+    };
+    match profile.find_projects(project).as_slice() {
+        [key] => Ok(key.to_string()),
+        [] if allow_unknown_projects => Ok(project.trim().to_string()),
+        [] => Err(format!(
+            "project '{}' is not configured, pass --allow-unknown-projects to use it anyway",
+            project
+        )),
+        keys => Err(format!(
+            "several projects match '{}': {}, use the project key",
+            project,
+            keys.join(", ")
+        )),
+    }
+}
+
+/// `bulk-create` flags that apply to every line
+struct BulkOptions<'a> {
+    default_project: Option<&'a str>,
+    default_issue_type: Option<&'a str>,
+    concurrency: usize,
+    no_default_labels: bool,
+    allow_unknown_projects: bool,
+}
+
+/// Checks every line before sending anything, then creates the issues with at most
+/// `concurrency` requests in flight. Results are printed grouped by project, in file
+/// order within each project
+async fn bulk_create(
+    client: &JiraClient,
+    config_path: &Path,
+    file: &Path,
+    options: &BulkOptions<'_>,
+) -> ExitCode {
+    let BulkOptions {
+        default_project,
+        default_issue_type,
+        concurrency,
+        no_default_labels,
+        allow_unknown_projects,
+    } = *options;
+    let rows = match read_bulk_file(file) {
+        Ok(rows) if rows.is_empty() => {
+            say!("❌ No issues found in {}", file.display());
+            return ExitCode::FAILURE;
+        }
+        Ok(rows) => rows,
+        Err(problems) => {
+            say!("❌ Nothing was created, fix these lines first:");
+            for problem in problems {
+                say!("   {}", problem);
+            }
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut problems = Vec::new();
+    let mut rows_with_project = Vec::new();
+    for (line, issue) in rows {
+        match bulk_project(
+            client.profile(),
+            issue.project.as_deref(),
+            default_project,
+            allow_unknown_projects,
+        ) {
+            Ok(project) => rows_with_project.push((line, project, issue)),
+            Err(problem) => problems.push((line, problem)),
+        }
+    }
+
+    // Projects in the order they first appear, which is also the order of the summary
+    let mut projects: Vec<String> = Vec::new();
+    for (_, project, _) in &rows_with_project {
+        if !projects.contains(project) {
+            projects.push(project.clone());
+        }
+    }
+    let mut issue_types = HashMap::new();
+    for project in &projects {
+        match fetch_issue_types(client, project).await {
+            Ok(types) => {
+                issue_types.insert(project.clone(), types);
+            }
+            Err(e) => return e.report(),
+        }
+    }
+    let priorities = if rows_with_project
+        .iter()
+        .any(|(_, _, issue)| issue.priority.is_some())
+    {
+        match fetch_priorities(client, projects.first().map_or("", String::as_str)).await {
+            Ok(priorities) => priorities,
+            Err(e) => return e.report(),
+        }
+    } else {
+        Vec::new()
+    };
+
+    let mut prepared = Vec::new();
+    for (line, project, issue) in rows_with_project {
+        let project_issue_type = client
+            .profile()
+            .projects
+            .get(&project)
+            .and_then(|project| project.default_issue_type.as_deref());
+        let Some(issue_type) = issue
+            .issue_type
+            .as_deref()
+            .or(default_issue_type)
+            .or(project_issue_type)
+        else {
+            problems.push((
+                line,
+                format!(
+                    "no \"type\", pass --type or set a default issue type for {}",
+                    project
+                ),
+            ));
+            continue;
+        };
+        match prepare_bulk_issue(
+            client.profile(),
+            &project,
+            &issue_types[&project],
+            &priorities,
+            issue_type,
+            no_default_labels,
+            &issue,
+        ) {
+            Ok((issue_type_id, priority_id, labels)) => prepared.push(PreparedIssue {
+                line,
+                project,
+                issue,
+                issue_type_id,
+                priority_id,
+                labels,
+            }),
+            Err(e) => problems.push((line, e.to_string())),
+        }
+    }
+    if !problems.is_empty() {
+        // Project problems are found in an earlier pass than the rest
+        problems.sort_by_key(|(line, _)| *line);
+        say!("❌ Nothing was created, fix these lines first:");
+        for (line, problem) in problems {
+            say!("   line {}: {}", line, problem);
+        }
+        return ExitCode::FAILURE;
+    }
+
+    let my_id = if prepared
+        .iter()
+        .any(|prepared| prepared.issue.assignee.as_deref().is_some_and(is_me))
+    {
+        match client.get_myself().await {
+            Ok(me) => Some(me.id().to_string()),
+            Err(e) => {
+                say!("❌ Could not look up your account: {}", e);
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        None
+    };
+    let my_id = my_id.as_deref();
+
+    say!(
+        "🚀 Creating {} issue(s) in {}, {} at a time...",
+        prepared.len(),
+        projects.join(", "),
+        concurrency
+    );
+    // `buffered` keeps the results in input order while requests overlap
+    let results: Vec<Result<CreatedIssue, JiraClientError>> = stream::iter(&prepared)
+        .map(|prepared| async move {
+            client
+                .create_issue(&NewIssue {
+                    project_key: &prepared.project,
+                    summary: prepared.issue.title.trim(),
+                    description: prepared
+                        .issue
+                        .description
+                        .as_deref()
+                        .filter(|desc| !desc.trim().is_empty()),
+                    issue_type_id: &prepared.issue_type_id,
+                    assignee: prepared.issue.assignee.as_deref().map(|assignee| {
+                        if is_me(assignee) {
+                            my_id.unwrap_or(assignee)
+                        } else {
+                            assignee
+                        }
+                    }),
+                    reporter: None,
+                    priority_id: prepared.priority_id.as_deref(),
+                    labels: &prepared.labels,
+                    component_ids: &[],
+                    fix_version_ids: &[],
+                    parent_key: None,
+                    due_date: None,
+                    sprint_id: None,
+                    original_estimate: None,
+                    remaining_estimate: None,
+                    environment: None,
+                    extra_fields: &[],
+                })
+                .await
+        })
+        .buffered(concurrency)
+        .collect()
+        .await;
+
+    let mut failed = 0;
+    for project in &projects {
+        if projects.len() > 1 {
+            say!("\n📁 {}", project);
+        }
+        for (prepared, result) in prepared
+            .iter()
+            .zip(&results)
+            .filter(|(prepared, _)| prepared.project == *project)
+        {
+            match result {
+                Ok(issue) => {
+                    say!("✅ {} {}", issue.key, issue.summary);
+                    say!("   🔗 {}", issue.url);
+                    record_history(config_path, issue);
+                }
+                Err(e) => {
+                    failed += 1;
+                    say!("❌ line {}: {}: {}", prepared.line, prepared.issue.title, e);
+                }
+            }
+        }
+    }
+    say!(
+        "\nCreated {} of {} issue(s)",
+        results.len() - failed,
+        results.len()
+    );
+
+    if failed == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Past this many issues `--all` warns that fetching may take a while
+const LARGE_SEARCH_WARNING: u32 = 5000;
+
+/// Fetches up to `limit` issues page by page, `None` fetches every matching issue
+async fn collect_issues<T, F, Fut>(
+    limit: Option<u32>,
+    mut fetch_page: F,
+) -> Result<Vec<T>, JiraClientError>
+where
+    F: FnMut(u32, u32) -> Fut,
+    Fut: Future<Output = Result<SearchPage<T>, JiraClientError>>,
+{
+    let mut issues = Vec::new();
+    let mut start_at = 0;
+    loop {
+        let wanted = limit.map_or(MAX_SEARCH_PAGE, |limit| {
+            (limit - issues.len() as u32).min(MAX_SEARCH_PAGE)
+        });
+        if wanted == 0 {
+            return Ok(issues);
+        }
+
+        let page = fetch_page(start_at, wanted).await?;
+        if start_at == 0 && limit.is_none() && page.total > LARGE_SEARCH_WARNING {
+            esay!(
+                "⚠️  The query matches {} issues, fetching all of them may take a while and use a lot of memory",
+                page.total
+            );
+        }
+        let first_page = start_at == 0;
+        let is_last = page.is_last();
+        let total = page.total;
+        start_at = page.next_start();
+        issues.extend(page.issues);
+
+        let expected = limit.map_or(total, |limit| limit.min(total));
+        let done = is_last || issues.len() as u32 >= expected;
+        // A result that fits in one request needs no progress
+        if !(first_page && done) {
+            esay!("🔍 Fetched {} of {} issues...", issues.len(), expected);
+        }
+        if done {
+            return Ok(issues);
+        }
+    }
+}
+
+/// Table of raw search results with one column per requested field
+fn print_fields_table(issues: &[Value], fields: &[String], format: OutputFormat) {
+    let rows: Vec<Vec<String>> = issues
+        .iter()
+        .map(|issue| {
+            let mut row = vec![issue["key"].as_str().unwrap_or("-").to_string()];
+            row.extend(
+                fields
+                    .iter()
+                    .map(|field| field_text(&issue["fields"][field.trim()])),
+            );
+            row.push(field_text(&issue["fields"]["summary"]));
+            row
+        })
+        .collect();
+
+    if format == OutputFormat::Csv {
+        let header = std::iter::once("key")
+            .chain(fields.iter().map(|field| field.trim()))
+            .chain(["summary"]);
+        println!("{}", csv_row(header));
+        for row in &rows {
+            println!("{}", csv_row(row.iter().map(String::as_str)));
+        }
+        return;
+    }
+
+    let mut header = vec!["KEY".to_string()];
+    header.extend(fields.iter().map(|field| field.trim().to_uppercase()));
+    header.push("SUMMARY".to_string());
+    // The last column is left unpadded
+    let widths: Vec<usize> = (0..header.len() - 1)
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .chain([header[column].chars().count()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    for row in std::iter::once(&header).chain(&rows) {
+        let mut line = String::new();
+        for (cell, width) in row.iter().zip(&widths) {
+            line.push_str(&format!("{:width$}  ", cell, width = width));
+        }
+        line.push_str(row.last().map(String::as_str).unwrap_or_default());
+        say!("{}", line);
+    }
+}
+
+/// Readable form of a field value: names of objects, comma-joined lists
+fn field_text(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::String(text) => text.clone(),
+        Value::Array(values) => {
+            let items: Vec<String> = values.iter().map(field_text).collect();
+            if items.is_empty() {
+                "-".to_string()
+            } else {
+                items.join(", ")
+            }
+        }
+        Value::Object(object) => ["displayName", "name", "value", "key"]
+            .iter()
+            .find_map(|name| object.get(*name).and_then(Value::as_str))
+            .map(str::to_string)
+            .unwrap_or_else(|| value.to_string()),
+        other => other.to_string(),
+    }
+}
+
+async fn fetch_issue_types(
+    client: &JiraClient,
+    project: &str,
+) -> Result<Vec<IssueType>, IssueCreateError> {
+    say!(
+        "🔍 Fetching available issue types for project {}...",
+        project
+    );
+
+    match client.get_project_issue_types(project).await {
+        Ok(types) => {
+            if types.is_empty() {
+                Err(IssueCreateError::IssueTypesNotFound(project.to_string()))
+            } else {
+                say!(
+                    "✅ Found {} issue type(s) for project {project}",
+                    types.len()
+                );
+                Ok(types)
+            }
+        }
+        Err(e) => Err(IssueCreateError::JiraClient(
+            project.to_string(),
+            format!("Jira client error: {}", e),
+        )),
+    }
+}
+
+async fn fetch_priorities(
+    client: &JiraClient,
+    project: &str,
+) -> Result<Vec<Priority>, IssueCreateError> {
+    client.get_priorities().await.map_err(|e| {
+        IssueCreateError::JiraClient(project.to_string(), format!("Jira client error: {}", e))
+    })
+}
+
+/// Resolves a priority given by id or by case-insensitive name
+async fn resolve_priority(
+    client: &JiraClient,
+    project: &str,
+    name_or_id: &str,
+) -> Result<Priority, IssueCreateError> {
+    fetch_priorities(client, project)
+        .await?
+        .into_iter()
+        .find(|p| p.id == name_or_id || p.name.eq_ignore_ascii_case(name_or_id))
+        .ok_or_else(|| IssueCreateError::UnknownPriority(name_or_id.to_string()))
+}
+
+async fn fetch_components(
+    client: &JiraClient,
+    project: &str,
+) -> Result<Vec<Component>, IssueCreateError> {
+    client.get_project_components(project).await.map_err(|e| {
+        IssueCreateError::JiraClient(project.to_string(), format!("Jira client error: {}", e))
+    })
+}
+
+/// Resolves components given by id or by case-insensitive name
+async fn resolve_components(
+    client: &JiraClient,
+    project: &str,
+    names_or_ids: &[String],
+) -> Result<Vec<Component>, IssueCreateError> {
+    if names_or_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let components = fetch_components(client, project).await?;
+    names_or_ids
+        .iter()
+        .map(|name_or_id| {
+            components
+                .iter()
+                .find(|c| c.id == *name_or_id || c.name.eq_ignore_ascii_case(name_or_id))
+                .cloned()
+                .ok_or_else(|| {
+                    IssueCreateError::UnknownComponent(name_or_id.clone(), project.to_string())
+                })
+        })
+        .collect()
+}
+
+/// Offers the project components when it has any, nothing selected skips the field
+async fn prompt_components(
+    client: &JiraClient,
+    project: &str,
+) -> Result<Vec<Component>, IssueCreateError> {
+    let components = fetch_components(client, project).await?;
+    if components.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let component_options: Vec<String> = components.iter().map(|c| c.name.clone()).collect();
+    let selected_options = MultiSelect::new("Components:", component_options)
+        .with_help_message("Space to select, enter to confirm, nothing selected skips")
+        .prompt()?;
+
+    Ok(components
+        .into_iter()
+        .filter(|c| selected_options.contains(&c.name))
+        .collect())
+}
+
+/// Released and archived versions are left out unless `include_released` is set
+async fn fetch_versions(
+    client: &JiraClient,
+    project: &str,
+    include_released: bool,
+) -> Result<Vec<Version>, IssueCreateError> {
+    let mut versions = client.get_project_versions(project).await.map_err(|e| {
+        IssueCreateError::JiraClient(project.to_string(), format!("Jira client error: {}", e))
+    })?;
+    if !include_released {
+        versions.retain(|v| !v.released && !v.archived);
+    }
+    Ok(versions)
+}
+
+/// Resolves fix versions given by id or by case-insensitive name
+async fn resolve_versions(
+    client: &JiraClient,
+    project: &str,
+    names_or_ids: &[String],
+    include_released: bool,
+) -> Result<Vec<Version>, IssueCreateError> {
+    if names_or_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Fetched in full to tell a released version apart from a typo
+    let versions = fetch_versions(client, project, true).await?;
+    names_or_ids
+        .iter()
+        .map(|name_or_id| {
+            let version = versions
+                .iter()
+                .find(|v| v.id == *name_or_id || v.name.eq_ignore_ascii_case(name_or_id))
+                .ok_or_else(|| {
+                    IssueCreateError::UnknownVersion(name_or_id.clone(), project.to_string())
+                })?;
+            if !include_released && (version.released || version.archived) {
+                return Err(IssueCreateError::ReleasedVersion(version.name.clone()));
+            }
+            Ok(version.clone())
+        })
+        .collect()
+}
+
+/// Offers the project versions when it has any, nothing selected skips the field
+async fn prompt_versions(
+    client: &JiraClient,
+    project: &str,
+    include_released: bool,
+) -> Result<Vec<Version>, IssueCreateError> {
+    let versions = fetch_versions(client, project, include_released).await?;
+    if versions.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let version_options: Vec<String> = versions.iter().map(|v| v.name.clone()).collect();
+    let selected_options = MultiSelect::new("Fix versions:", version_options)
+        .with_help_message("Space to select, enter to confirm, nothing selected skips")
+        .prompt()?;
+
+    Ok(versions
+        .into_iter()
+        .filter(|v| selected_options.contains(&v.name))
+        .collect())
+}
+
+/// Trims labels and drops empty ones, Jira rejects labels containing spaces
+fn validate_labels<S: AsRef<str>>(labels: &[S]) -> Result<Vec<String>, IssueCreateError> {
+    labels
+        .iter()
+        .map(|label| label.as_ref().trim())
+        .filter(|label| !label.is_empty())
+        .map(|label| {
+            if label.contains(char::is_whitespace) {
+                Err(IssueCreateError::InvalidLabel(label.to_string()))
+            } else {
+                Ok(label.to_string())
+            }
+        })
+        .collect()
+}
+
+/// Puts the project's default labels in front of the given ones, dropping duplicates
+fn with_default_labels(
+    profile: &Profile,
+    project: &str,
+    labels: Vec<String>,
+    no_default_labels: bool,
+) -> Result<Vec<String>, IssueCreateError> {
+    let defaults = match profile.projects.get(project) {
+        Some(project) if !no_default_labels => validate_labels(&project.default_labels)?,
+        _ => Vec::new(),
+    };
+    let mut merged: Vec<String> = Vec::new();
+    for label in defaults.into_iter().chain(labels) {
+        if !merged.contains(&label) {
+            merged.push(label);
+        }
+    }
+    Ok(merged)
+}
+
+fn prompt_labels() -> Result<Vec<String>, IssueCreateError> {
+    loop {
+        let input = Text::new("Labels (comma-separated, leave blank to skip):")
+            .with_placeholder("e.g. frontend, regression")
+            .prompt()?;
+
+        let labels: Vec<&str> = input.split(',').collect();
+        match validate_labels(&labels) {
+            Ok(labels) => return Ok(labels),
+            Err(e) => say!("❌ {}. Try again", e),
+        }
+    }
+}
+
+/// Opens a temporary file holding `initial` in the editor and returns what was saved, `None`
+/// when the editor can't be started or exits with an error so the caller can prompt inline
+fn edit_in_editor(editor: &str, initial: &str) -> Option<String> {
+    let path = match create_temp_file("fast-task-description", ".md", initial) {
+        Ok(path) => path,
+        Err(err) => {
+            say!("⚠️ Could not create a temporary file: {}", err);
+            return None;
+        }
+    };
+
+    let content = match run_editor(editor, &path) {
+        Ok(status) if status.success() => fs::read_to_string(&path).ok(),
+        Ok(status) => {
+            say!(
+                "⚠️ {} exited with {}, enter the description inline",
+                editor,
+                status
+            );
+            None
+        }
+        Err(err) => {
+            say!(
+                "⚠️ Could not start {}: {}, enter the description inline",
+                editor,
+                err
+            );
+            None
+        }
+    };
+    let _ = fs::remove_file(&path);
+    content
+}
+
+/// Creates a new file with a random name in the temp directory, readable only by the
+/// user. `create_new` refuses a path someone else prepared on a shared /tmp, a symlink
+/// there could otherwise redirect or expose the content
+fn create_temp_file(prefix: &str, suffix: &str, content: &str) -> io::Result<PathBuf> {
+    let mut random = [0u8; 8];
+    getrandom::getrandom(&mut random).map_err(|err| io::Error::other(err.to_string()))?;
+    let name: String = random.iter().map(|byte| format!("{:02x}", byte)).collect();
+    let path = env::temp_dir().join(format!("{}-{}{}", prefix, name, suffix));
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(&path)?.write_all(content.as_bytes())?;
+    Ok(path)
+}
+
+/// Finds an issue type by id or by case-insensitive name
+fn find_issue_type<'a>(issue_types: &'a [IssueType], name_or_id: &str) -> Option<&'a IssueType> {
+    issue_types
+        .iter()
+        .find(|it| it.id == name_or_id || it.name.eq_ignore_ascii_case(name_or_id))
+}
+
+async fn create_issue_noninteractive(
+    client: &JiraClient,
+    project: &str,
+    title: &str,
+    issue_type: &str,
+    args: &CreateArgs,
+) -> Result<Option<CreatedIssue>, IssueCreateError> {
+    if !client.profile().projects.contains_key(project) {
+        return Err(IssueCreateError::UnknownProject(project.to_string()));
+    }
+    if title.trim().is_empty() {
+        return Err(IssueCreateError::EmptyTitle);
+    }
+    if !is_single_line(title) {
+        return Err(IssueCreateError::InvalidSummary);
+    }
+
+    let issue_types = fetch_issue_types(client, project).await?;
+    let selected_issue_type = find_issue_type(&issue_types, issue_type).ok_or_else(|| {
+        IssueCreateError::UnknownIssueType(issue_type.to_string(), project.to_string())
+    })?;
+    if selected_issue_type.subtask && args.parent.is_none() {
+        return Err(IssueCreateError::MissingParent(
+            selected_issue_type.name.clone(),
+        ));
+    }
+    check_duplicate(client, project, title, args, true).await?;
+
+    let labels = with_default_labels(
+        client.profile(),
+        project,
+        validate_labels(&args.labels)?,
+        args.no_default_labels,
+    )?;
+    let component_ids: Vec<String> = resolve_components(client, project, &args.components)
+        .await?
+        .into_iter()
+        .map(|c| c.id)
+        .collect();
+    let fix_version_ids: Vec<String> =
+        resolve_versions(client, project, &args.fix_versions, args.include_released)
+            .await?
+            .into_iter()
+            .map(|v| v.id)
+            .collect();
+    let priority_id = match args.priority {
+        Some(ref priority) => Some(resolve_priority(client, project, priority).await?.id),
+        None => None,
+    };
+    let sprint_id = match args.sprint {
+        Some(ref sprint) => Some(resolve_sprint(client, project, sprint).await?),
+        None => None,
+    };
+    let assignee = args.assignee(client.profile(), project);
+
+    submit_issue(
+        client,
+        &NewIssue {
+            project_key: project,
+            summary: title,
+            description: args
+                .description
+                .as_deref()
+                .filter(|desc| !desc.trim().is_empty()),
+            issue_type_id: selected_issue_type.id.as_str(),
+            assignee: assignee.as_deref(),
+            reporter: args.reporter.as_deref(),
+            priority_id: priority_id.as_deref(),
+            labels: &labels,
+            component_ids: &component_ids,
+            fix_version_ids: &fix_version_ids,
+            parent_key: args.parent.as_deref(),
+            due_date: args.due_date,
+            sprint_id,
+            original_estimate: args.original_estimate.as_deref(),
+            remaining_estimate: args.remaining_estimate.as_deref(),
+            environment: args.environment.as_deref(),
+            extra_fields: &args.fields,
+        },
+        &args.watchers,
+        args,
+    )
+    .await
+}
+
+/// `--dedupe`: stops at an open issue with the same title unless the user wants
+/// another one. Under `--yes` only `--dedupe-strict` checks, and a duplicate aborts
+async fn check_duplicate(
+    client: &JiraClient,
+    project: &str,
+    title: &str,
+    args: &CreateArgs,
+    assume_yes: bool,
+) -> Result<(), IssueCreateError> {
+    if !(args.dedupe_strict || args.dedupe && !assume_yes) {
+        return Ok(());
+    }
+    // Text search treats punctuation as operators, the exact match is checked below
+    let terms: String = title
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+    let terms: Vec<&str> = terms.split_whitespace().collect();
+    if terms.is_empty() {
+        return Ok(());
+    }
+    let jql = format!(
+        "project = {} AND summary ~ {} AND statusCategory != Done ORDER BY created DESC",
+        jql_string(project),
+        jql_string(&terms.join(" "))
+    );
+    // Few open issues share the words of a title, the first page is enough
+    let candidates = client
+        .search_issues(&jql, 0, MAX_SEARCH_PAGE)
+        .await
+        .map(|page| page.issues)
+        .map_err(|e| {
+            IssueCreateError::JiraClient(
+                project.to_string(),
+                format!("Could not look for duplicates: {}", e),
+            )
+        })?;
+    let title = title.trim().to_lowercase();
+    let Some(existing) = candidates
+        .into_iter()
+        .find(|issue| issue.summary.trim().to_lowercase() == title)
+    else {
+        return Ok(());
+    };
+
+    let url = client.profile().issue_url(&existing.key);
+    if !assume_yes {
+        say!(
+            "⚠️ {} has the same title and is {}: {}",
+            existing.key,
+            existing.status,
+            url
+        );
+        if Confirm::new("Create another issue anyway?")
+            .with_default(false)
+            .prompt()?
+        {
+            return Ok(());
+        }
+    }
+    Err(IssueCreateError::Duplicate(existing.key, url))
+}
+
+/// Creates the issue, or only prints the request payload on a dry run.
+/// Watchers that can't be added are reported, the issue exists regardless
+async fn submit_issue(
+    client: &JiraClient,
+    issue: &NewIssue<'_>,
+    watchers: &[String],
+    args: &CreateArgs,
+) -> Result<Option<CreatedIssue>, IssueCreateError> {
+    if args.dry_run {
+        let payload = client.issue_payload(issue);
+        say!("\n🧪 DRY RUN, nothing is sent to Jira. Request payload:");
+        // The payload is the result of `--json --dry-run`, it stays on stdout
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&payload).unwrap_or_else(|_| payload.to_string())
+        );
+        return Ok(None);
+    }
+
+    say!("\n🚀 Creating issue...");
+    let created = client.create_issue(issue).await.map_err(|e| match e {
+        JiraClientError::JiraValidationError { ref errors, .. }
+            if issue.reporter.is_some() && errors.contains_key("reporter") =>
+        {
+            IssueCreateError::ReporterNotSettable(
+                errors["reporter"].trim_end_matches('.').to_string(),
+            )
+        }
+        JiraClientError::InvalidSummary => IssueCreateError::InvalidSummary,
+        e => IssueCreateError::JiraClient(
+            issue.project_key.to_string(),
+            format!("Jira client error: {}", e),
+        ),
+    })?;
+
+    for watcher in watchers {
+        if let Err(e) = client.add_watcher(&created.key, watcher).await {
+            // stderr keeps `--json` output parseable
+            esay!(
+                "⚠️ Could not add watcher {} to {}: {}",
+                watcher,
+                created.key,
+                e
+            );
+        }
+    }
+    Ok(Some(created))
+}
+
+/// Create screen fields of the issue type. Older instances lack the endpoint, nothing
+/// is known to be required then and Jira reports what is missing on create
+async fn fetch_create_fields(
+    client: &JiraClient,
+    project: &str,
+    issue_type: &IssueType,
+) -> Vec<CreateField> {
+    match client.get_create_fields(project, &issue_type.id).await {
+        Ok(fields) => fields,
+        Err(e) => {
+            debug!(project, issue_type = %issue_type.name, error = %e, "createmeta unavailable");
+            Vec::new()
+        }
+    }
+}
+
+/// Fields `create` sets itself or prompts for when the issue type requires them
+const PROMPTED_FIELDS: &[&str] = &[
+    "summary",
+    "issuetype",
+    "project",
+    "parent",
+    "description",
+    "environment",
+    "duedate",
+    "timetracking",
+    "priority",
+    "labels",
+    "components",
+    "fixVersions",
+    "assignee",
+    "reporter",
+];
+
+/// Required fields without a prompt can still be sent with `--field`, says which ones
+fn warn_unsupported_required_fields(
+    create_fields: &[CreateField],
+    issue_type: &IssueType,
+    args: &CreateArgs,
+) {
+    let missing: Vec<&CreateField> = create_fields
+        .iter()
+        .filter(|field| field.must_be_set())
+        .filter(|field| !PROMPTED_FIELDS.contains(&field.field_id.as_str()))
+        .filter(|field| !args.fields.iter().any(|(key, _)| *key == field.field_id))
+        .collect();
+    if missing.is_empty() {
+        return;
+    }
+    let names: Vec<String> = missing
+        .iter()
+        .map(|field| format!("{} ({})", field.name, field.field_id))
+        .collect();
+    say!(
+        "⚠️ {} issues require fields fast-task can't prompt for: {}",
+        issue_type.name,
+        names.join(", ")
+    );
+    say!(
+        "💡 Pass them with --field, e.g. --field {}=VALUE",
+        missing[0].field_id
+    );
+}
+
+fn prompt_required_text(message: &str, placeholder: &str) -> Result<String, IssueCreateError> {
+    loop {
+        let text = Text::new(message)
+            .with_help_message("Required for this issue type")
+            .with_placeholder(placeholder)
+            .prompt()?;
+        if !text.trim().is_empty() {
+            return Ok(text.trim().to_string());
+        }
+        say!("❌ This field cannot be empty. Try again");
+    }
+}
+
+/// Asks until the input passes `parse`, e.g. `parse_due`
+fn prompt_parsed<T>(
+    message: &str,
+    help: &str,
+    parse: fn(&str) -> Result<T, String>,
+) -> Result<T, IssueCreateError> {
+    loop {
+        let input = Text::new(message)
+            .with_help_message(&format!("Required for this issue type, {}", help))
+            .prompt()?;
+        match parse(&input) {
+            Ok(value) => return Ok(value),
+            Err(e) => say!("❌ {}. Try again", e),
+        }
+    }
+}
+
+/// `prompt_user`, asking again after a blank answer when the field is required
+async fn prompt_required_user(
+    client: &JiraClient,
+    project: &str,
+    role: &str,
+    required: bool,
+) -> Result<Option<String>, IssueCreateError> {
+    loop {
+        let user = prompt_user(client, project, role).await?;
+        if user.is_some() || !required {
+            return Ok(user);
+        }
+        say!("❌ {} is required for this issue type. Try again", role);
+    }
+}
+
+/// Asks for a parent issue key until one that exists in Jira is entered
+async fn prompt_parent(client: &JiraClient, project: &str) -> Result<String, IssueCreateError> {
+    loop {
+        let parent_key = Text::new("Parent issue key:")
+            .with_help_message("Sub-tasks must be created under an existing issue")
+            .with_placeholder("e.g. ABC-12")
+            .prompt()?;
+        let parent_key = parent_key.trim();
+
+        if parent_key.is_empty() {
+            say!("❌ Parent issue key cannot be empty. Try again");
+            continue;
+        }
+
+        match client.get_issue_details(parent_key).await {
+            Ok(issue) => {
+                say!("✓ Parent: {} - {}", issue.key, issue.summary);
+                return Ok(issue.key);
+            }
+            Err(JiraClientError::Response(StatusCode::NOT_FOUND, _)) => {
+                say!("❌ Issue {} was not found. Try again", parent_key);
+            }
+            Err(e) => {
+                return Err(IssueCreateError::JiraClient(
+                    project.to_string(),
+                    format!("Jira client error: {}", e),
+                ));
+            }
+        }
+    }
+}
+
+async fn fetch_sprints(
+    client: &JiraClient,
+    project: &str,
+    board_id: u64,
+) -> Result<Vec<Sprint>, IssueCreateError> {
+    client.get_board_sprints(board_id).await.map_err(|e| {
+        IssueCreateError::JiraClient(project.to_string(), format!("Jira client error: {}", e))
+    })
+}
+
+/// Takes a sprint id as is, names are looked up on the project's board
+async fn resolve_sprint(
+    client: &JiraClient,
+    project: &str,
+    id_or_name: &str,
+) -> Result<u64, IssueCreateError> {
+    if let Ok(id) = id_or_name.trim().parse() {
+        return Ok(id);
+    }
+
+    let board_id = client
+        .profile()
+        .projects
+        .get(project)
+        .and_then(|project| project.board_id)
+        .ok_or_else(|| IssueCreateError::MissingBoard(project.to_string()))?;
+    fetch_sprints(client, project, board_id)
+        .await?
+        .into_iter()
+        .find(|sprint| sprint.name.eq_ignore_ascii_case(id_or_name.trim()))
+        .map(|sprint| sprint.id)
+        .ok_or_else(|| IssueCreateError::UnknownSprint(id_or_name.to_string(), project.to_string()))
+}
+
+/// Offers the board's active and future sprints, the first option leaves the backlog
+async fn prompt_sprint(
+    client: &JiraClient,
+    project: &str,
+    board_id: u64,
+) -> Result<Option<u64>, IssueCreateError> {
+    const BACKLOG_OPTION: &str = "Backlog";
+
+    let sprints = fetch_sprints(client, project, board_id).await?;
+    if sprints.is_empty() {
+        return Ok(None);
+    }
+
+    let mut sprint_options = vec![BACKLOG_OPTION.to_string()];
+    sprint_options.extend(
+        sprints
+            .iter()
+            .map(|sprint| format!("{} ({})", sprint.name, sprint.state)),
+    );
+    let selected = Select::new("Sprint:", sprint_options)
+        .with_help_message("Select the sprint to plan the issue into")
+        .raw_prompt()?;
+
+    Ok(selected.index.checked_sub(1).map(|index| sprints[index].id))
+}
+
+/// Lets the user pick a priority, the first option keeps the project default
+/// A required priority has no "Default" option, Jira has no default to fall back to
+async fn prompt_priority(
+    client: &JiraClient,
+    project: &str,
+    required: bool,
+) -> Result<Option<Priority>, IssueCreateError> {
+    const DEFAULT_OPTION: &str = "Default";
+
+    let priorities = fetch_priorities(client, project).await?;
+    if priorities.is_empty() {
+        return Ok(None);
+    }
+
+    let mut priority_options = Vec::new();
+    if !required {
+        priority_options.push(DEFAULT_OPTION.to_string());
+    }
+    priority_options.extend(priorities.iter().map(|p| p.name.clone()));
+    let selected_option = Select::new("Priority:", priority_options)
+        .with_help_message("Select the priority of the issue")
+        .prompt()?;
+
+    Ok(priorities.into_iter().find(|p| p.name == selected_option))
+}
+
+/// Searches assignable users until one is picked for `role`, a blank query skips the field
+async fn prompt_user(
+    client: &JiraClient,
+    project: &str,
+    role: &str,
+) -> Result<Option<String>, IssueCreateError> {
+    loop {
+        let query = Text::new(&format!("{} (leave blank to skip):", role))
+            .with_help_message("Type a name or email to search assignable users")
+            .prompt()?;
+
+        if query.trim().is_empty() {
+            return Ok(None);
+        }
+
+        let users = client
+            .search_assignable_users(project, query.trim())
+            .await
+            .map_err(|e| {
+                IssueCreateError::JiraClient(
+                    project.to_string(),
+                    format!("Jira client error: {}", e),
+                )
+            })?;
+        if users.is_empty() {
+            say!(
+                "❌ No assignable users found for '{}'. Try again",
+                query.trim()
+            );
+            continue;
+        }
+
+        let user_options: Vec<String> = users
+            .iter()
+            .map(|user| match user.email_address {
+                Some(ref email) => format!("{} <{}>", user.display_name, email),
+                None => user.display_name.clone(),
+            })
+            .collect();
+        let selected_option = Select::new(&format!("{}:", role), user_options.clone()).prompt()?;
+        let selected_index = user_options
+            .iter()
+            .position(|option| option == &selected_option)
+            .ok_or(IssueCreateError::SelectOption)?;
+
+        return Ok(Some(users[selected_index].id().to_string()));
+    }
+}
+
+/// Collects watchers from assignable user searches until a blank query is entered
+async fn prompt_watchers(
+    client: &JiraClient,
+    project: &str,
+) -> Result<Vec<String>, IssueCreateError> {
+    let mut watchers: Vec<String> = Vec::new();
+    loop {
+        let query = Text::new("Add watchers (leave blank to skip):")
+            .with_help_message("Type a name or email to search users")
+            .prompt()?;
+
+        if query.trim().is_empty() {
+            return Ok(watchers);
+        }
+
+        let users = client
+            .search_assignable_users(project, query.trim())
+            .await
+            .map_err(|e| {
+                IssueCreateError::JiraClient(
+                    project.to_string(),
+                    format!("Jira client error: {}", e),
+                )
+            })?;
+        if users.is_empty() {
+            say!("❌ No users found for '{}'. Try again", query.trim());
+            continue;
+        }
+
+        let user_options: Vec<String> = users
+            .iter()
+            .map(|user| match user.email_address {
+                Some(ref email) => format!("{} <{}>", user.display_name, email),
+                None => user.display_name.clone(),
+            })
+            .collect();
+        let selected = MultiSelect::new("Watchers:", user_options)
+            .with_help_message("Space to select, enter to confirm")
+            .raw_prompt()?;
+        for option in selected {
+            let id = users[option.index].id().to_string();
+            if !watchers.contains(&id) {
+                watchers.push(id);
+            }
+        }
+    }
+}
+
+/// Maps `--project` to a configured key, it may also be the project's display name
+fn resolve_project(profile: &Profile, key_or_name: &str) -> Result<String, IssueCreateError> {
+    let keys = profile.find_projects(key_or_name);
+    match keys.as_slice() {
+        [] => Err(IssueCreateError::UnknownProject(key_or_name.to_string())),
+        [key] => Ok(key.to_string()),
+        _ => {
+            let options: Vec<String> = keys
+                .iter()
+                .map(|key| format!("{} - {}", key, profile.project_name(key)))
+                .collect();
+            let selected = Select::new(
+                &format!("Several projects match '{}':", key_or_name),
+                options,
+            )
+            .raw_prompt()?;
+            Ok(keys[selected.index].to_string())
+        }
+    }
+}
+
+async fn interactive_create_issue(
+    client: &JiraClient,
+    args: &CreateArgs,
+    assume_yes: bool,
+    last_project: Option<&str>,
+) -> Result<Option<CreatedIssue>, IssueCreateError> {
+    say!("🎯 Creating a new Jira issue \n");
+
+    let profile = client.profile();
+    let selected_project = match args.project {
+        Some(ref project) => {
+            if !profile.projects.contains_key(project) {
+                return Err(IssueCreateError::UnknownProject(project.clone()));
+            }
+            project.clone()
+        }
+        None => {
+            let last_project = last_project.filter(|key| profile.projects.contains_key(*key));
+            match last_project {
+                Some(project) if args.last => project.to_string(),
+                _ => {
+                    let project_keys: Vec<&String> = profile.projects.keys().collect();
+                    // The name is part of the option so typing it filters too
+                    let project_options: Vec<String> = project_keys
+                        .iter()
+                        .map(|key| format!("{} - {}", key, profile.project_name(key)))
+                        .collect();
+                    let starting_cursor = last_project
+                        .and_then(|last| project_keys.iter().position(|key| *key == last))
+                        .unwrap_or_default();
+                    let selected = Select::new("Which project?", project_options)
+                        .with_help_message(
+                            "Select the project where you want to create the issue, type to filter",
+                        )
+                        .with_page_size(PICKER_PAGE_SIZE)
+                        .with_starting_cursor(starting_cursor)
+                        .raw_prompt()?;
+                    project_keys[selected.index].clone()
+                }
+            }
+        }
+    };
+
+    say!(
+        "✓ Selected project: {} ({})",
+        selected_project,
+        profile.project_name(&selected_project)
+    );
+
+    let title = match args.title {
+        Some(ref title) => title.clone(),
+        None => {
+            let mut prompt = Text::new("Issue title:")
+                .with_help_message("Enter a brief, descriptive title for your issue")
+                .with_placeholder("e.g., Fix login button styling");
+            if let Some(ref prefix) = args.summary_prefix {
+                prompt = prompt.with_initial_value(prefix);
+            }
+            prompt.prompt()?
+        }
+    };
+
+    if title.trim().is_empty()
+        || args.summary_prefix.as_deref().map(str::trim) == Some(title.trim())
+    {
+        return Err(IssueCreateError::EmptyTitle);
+    }
+    if !is_single_line(&title) {
+        return Err(IssueCreateError::InvalidSummary);
+    }
+
+    let has_description = args.description.is_none()
+        && args.prompts_optional_fields()
+        && Confirm::new("Add description?")
+            .with_default(false)
+            .with_help_message("Press 'y' to add a detailed description")
+            .prompt()?;
+
+    let description = if has_description {
+        let initial = args
+            .description_template
+            .as_deref()
+            .map(|template| {
+                template
+                    .replace("{title}", title.trim())
+                    .replace("{project}", &selected_project)
+            })
+            .unwrap_or_default();
+        let edited = match env::var("EDITOR") {
+            Ok(editor) if !editor.trim().is_empty() => {
+                let use_editor = Confirm::new(&format!("Write the description in {}?", editor))
+                    .with_default(true)
+                    .prompt()?;
+                if use_editor {
+                    edit_in_editor(&editor, &initial)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+        let desc = match edited {
+            Some(desc) => desc,
+            None => Text::new("Issue description:")
+                .with_help_message("Provide detailed information about the issue")
+                .with_placeholder("Steps to reproduce, expected behavior, etc.")
+                .with_initial_value(&initial)
+                .prompt()?,
+        };
+
+        if desc.trim().is_empty() {
+            None
+        } else {
+            Some(desc)
+        }
+    } else {
+        args.description
+            .clone()
+            .filter(|desc| !desc.trim().is_empty())
+    };
+
+    let issue_types = fetch_issue_types(client, &selected_project).await?;
+
+    let default_issue_type = profile
+        .projects
+        .get(&selected_project)
+        .and_then(|project| project.default_issue_type.as_deref())
+        .and_then(|name| issue_types.iter().position(|it| it.name == name));
+
+    let selected_issue_type = match (
+        &args.issue_type,
+        default_issue_type.filter(|_| args.use_defaults),
+    ) {
+        (Some(issue_type), _) => find_issue_type(&issue_types, issue_type).ok_or_else(|| {
+            IssueCreateError::UnknownIssueType(issue_type.clone(), selected_project.clone())
+        })?,
+        (None, Some(default_index)) => &issue_types[default_index],
+        (None, None) => {
+            // Sub-task types are listed after the standard ones, the sort is stable
+            let mut order: Vec<usize> = (0..issue_types.len()).collect();
+            order.sort_by_key(|&index| issue_types[index].subtask);
+            let issue_type_options: Vec<String> = order
+                .iter()
+                .map(|&index| {
+                    let it = &issue_types[index];
+                    let name = if it.subtask {
+                        format!("↳ {}", it.name)
+                    } else {
+                        it.name.clone()
+                    };
+                    if let Some(ref description) = it.description {
+                        format!("{} - {}", name, truncate(description, 60))
+                    } else {
+                        name
+                    }
+                })
+                .collect();
+            let help_message = if issue_types.iter().any(|it| it.subtask) {
+                "Type to filter, ↳ marks sub-tasks that need a parent issue"
+            } else {
+                "Select the type of issue you're creating, type to filter"
+            };
+            let starting_cursor = default_issue_type
+                .and_then(|default| order.iter().position(|&index| index == default))
+                .unwrap_or_default();
+
+            let selected = Select::new("Issue type:", issue_type_options)
+                .with_help_message(help_message)
+                .with_page_size(PICKER_PAGE_SIZE)
+                .with_starting_cursor(starting_cursor)
+                .raw_prompt()?;
+
+            &issue_types[order[selected.index]]
+        }
+    };
+
+    // Required fields are prompted for even when the flags skip optional ones
+    let create_fields = fetch_create_fields(client, &selected_project, selected_issue_type).await;
+    let required = |field_id: &str| {
+        create_fields
+            .iter()
+            .any(|field| field.field_id == field_id && field.must_be_set())
+    };
+    warn_unsupported_required_fields(&create_fields, selected_issue_type, args);
+
+    let description = match description {
+        None if required("description") => Some(prompt_required_text(
+            "Issue description:",
+            "Steps to reproduce, expected behavior, etc.",
+        )?),
+        description => description,
+    };
+    let environment = match args.environment {
+        Some(ref environment) => Some(environment.clone()),
+        None if required("environment") => Some(prompt_required_text(
+            "Environment:",
+            "e.g. Chrome 126, macOS 14, staging",
+        )?),
+        None => None,
+    };
+    let due_date = match args.due_date {
+        Some(due_date) => Some(due_date),
+        None if required("duedate") => Some(prompt_parsed(
+            "Due date:",
+            "YYYY-MM-DD, today, tomorrow, +3d, +2w or next friday",
+            parse_due,
+        )?),
+        None => None,
+    };
+    let original_estimate = match args.original_estimate {
+        Some(ref estimate) => Some(estimate.clone()),
+        None if required("timetracking") => Some(prompt_parsed(
+            "Original estimate:",
+            "e.g. 3h or 1w 2d",
+            parse_estimate,
+        )?),
+        None => None,
+    };
+
+    let parent = match args.parent {
+        Some(ref parent) => Some(parent.clone()),
+        None if selected_issue_type.subtask => {
+            Some(prompt_parent(client, &selected_project).await?)
+        }
+        None => None,
+    };
+
+    let priority = match args.priority {
+        Some(ref priority) => Some(resolve_priority(client, &selected_project, priority).await?),
+        None if args.prompts_optional_fields() || required("priority") => {
+            prompt_priority(client, &selected_project, required("priority")).await?
+        }
+        None => None,
+    };
+
+    let labels = if args.labels.is_empty() && (args.prompts_optional_fields() || required("labels"))
+    {
+        loop {
+            let labels = with_default_labels(
+                client.profile(),
+                &selected_project,
+                prompt_labels()?,
+                args.no_default_labels,
+            )?;
+            if !labels.is_empty() || !required("labels") {
+                break labels;
+            }
+            say!(
+                "❌ {} issues need a label. Try again",
+                selected_issue_type.name
+            );
+        }
+    } else {
+        with_default_labels(
+            client.profile(),
+            &selected_project,
+            validate_labels(&args.labels)?,
+            args.no_default_labels,
+        )?
+    };
+
+    let components = if args.components.is_empty()
+        && (args.prompts_optional_fields() || required("components"))
+    {
+        loop {
+            let components = prompt_components(client, &selected_project).await?;
+            if !components.is_empty() || !required("components") {
+                break components;
+            }
+            say!(
+                "❌ {} issues need a component. Try again",
+                selected_issue_type.name
+            );
+        }
+    } else {
+        resolve_components(client, &selected_project, &args.components).await?
+    };
+    let component_ids: Vec<String> = components.iter().map(|c| c.id.clone()).collect();
+
+    let fix_versions = if args.fix_versions.is_empty()
+        && (args.prompts_optional_fields() || required("fixVersions"))
+    {
+        loop {
+            let versions =
+                prompt_versions(client, &selected_project, args.include_released).await?;
+            if !versions.is_empty() || !required("fixVersions") {
+                break versions;
+            }
+            say!(
+                "❌ {} issues need a fix version. Try again",
+                selected_issue_type.name
+            );
+        }
+    } else {
+        resolve_versions(
+            client,
+            &selected_project,
+            &args.fix_versions,
+            args.include_released,
+        )
+        .await?
+    };
+    let fix_version_ids: Vec<String> = fix_versions.iter().map(|v| v.id.clone()).collect();
+
+    let assignee = match args.assignee(profile, &selected_project) {
+        Some(assignee) => Some(assignee),
+        None if args.prompts_optional_fields() || required("assignee") => {
+            prompt_required_user(client, &selected_project, "Assignee", required("assignee"))
+                .await?
+        }
+        None => None,
+    };
+
+    let reporter = match args.reporter {
+        Some(ref reporter) => Some(reporter.clone()),
+        None if args.prompts_optional_fields() || required("reporter") => {
+            prompt_required_user(client, &selected_project, "Reporter", required("reporter"))
+                .await?
+        }
+        None => None,
+    };
+
+    let watchers = if args.watchers.is_empty() && args.prompts_optional_fields() {
+        prompt_watchers(client, &selected_project).await?
+    } else {
+        args.watchers.clone()
+    };
+
+    let board_id = profile
+        .projects
+        .get(&selected_project)
+        .and_then(|project| project.board_id);
+    let sprint_id = match (&args.sprint, board_id) {
+        (Some(sprint), _) => Some(resolve_sprint(client, &selected_project, sprint).await?),
+        (None, Some(board_id)) if args.prompts_optional_fields() => {
+            prompt_sprint(client, &selected_project, board_id).await?
+        }
+        _ => None,
+    };
+
+    say!("\n📋 Issue Summary:");
+    say!(
+        "   Project: {} ({})",
+        selected_project,
+        profile.project_name(&selected_project)
+    );
+    say!("   Title: {}", title);
+    if let Some(ref desc) = description {
+        say!("   Description: {}", truncate(desc, 53));
+    }
+    say!("   Type: {}", selected_issue_type.name);
+    if let Some(ref desc) = selected_issue_type.description {
+        say!("   Type Description: {}", desc);
+    }
+    if let Some(ref parent) = parent {
+        say!("   Parent: {}", parent);
+    }
+    if let Some(ref environment) = environment {
+        say!("   Environment: {}", truncate(environment, 53));
+    }
+    if let Some(ref priority) = priority {
+        say!("   Priority: {}", priority.name);
+    }
+    if let Some(ref assignee) = assignee {
+        say!("   Assignee: {}", assignee);
+    }
+    if let Some(ref reporter) = reporter {
+        say!("   Reporter: {}", reporter);
+    }
+    if !watchers.is_empty() {
+        say!("   Watchers: {}", watchers.join(", "));
+    }
+    if let Some(due_date) = due_date {
+        say!("   Due: {}", due_date);
+    }
+    if let Some(ref estimate) = original_estimate {
+        say!("   Estimate: {}", estimate);
+    }
+    if let Some(sprint_id) = sprint_id {
+        say!("   Sprint: {}", sprint_id);
+    }
+    if !labels.is_empty() {
+        say!("   Labels: {}", labels.join(", "));
+    }
+    if !components.is_empty() {
+        let component_names: Vec<&str> = components.iter().map(|c| c.name.as_str()).collect();
+        say!("   Components: {}", component_names.join(", "));
+    }
+    if !fix_versions.is_empty() {
+        let version_names: Vec<&str> = fix_versions.iter().map(|v| v.name.as_str()).collect();
+        say!("   Fix versions: {}", version_names.join(", "));
+    }
+
+    check_duplicate(client, &selected_project, &title, args, assume_yes).await?;
+
+    let confirm = assume_yes
+        || Confirm::new("Create this issue?")
+            .with_default(true)
+            .prompt()?;
 
     if !confirm {
         return Err(IssueCreateError::Canceled);
     }
-    println!("\n🚀 Creating issue...");
-    Ok(create_issue(
-        &client,
-        &selected_project,
-        &title,
-        description.as_deref(),
-        selected_issue_type.id.as_str(),
+    submit_issue(
+        client,
+        &NewIssue {
+            project_key: &selected_project,
+            summary: &title,
+            description: description.as_deref(),
+            issue_type_id: selected_issue_type.id.as_str(),
+            assignee: assignee.as_deref(),
+            reporter: reporter.as_deref(),
+            priority_id: priority.as_ref().map(|p| p.id.as_str()),
+            labels: &labels,
+            component_ids: &component_ids,
+            fix_version_ids: &fix_version_ids,
+            parent_key: parent.as_deref(),
+            due_date,
+            sprint_id,
+            original_estimate: original_estimate.as_deref(),
+            remaining_estimate: args.remaining_estimate.as_deref(),
+            environment: environment.as_deref(),
+            extra_fields: &args.fields,
+        },
+        &watchers,
+        args,
     )
     .await
-    .map_err(|e| {
-        IssueCreateError::JiraClient(selected_option, format!("Jira client error: {}", e))
-    }))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_keeps_short_text() {
+        assert_eq!(truncate("Короткий", 10), "Короткий");
+        assert_eq!(truncate("ровно", 5), "ровно");
+    }
+
+    #[test]
+    fn truncate_cuts_multibyte_text_at_the_limit() {
+        // Slicing bytes at 10 used to land inside a two-byte letter and panic
+        assert_eq!(truncate("Исправить кнопку входа", 10), "Исправи...");
+        assert_eq!(truncate("🚀🚀🚀🚀🚀🚀", 5), "🚀🚀...");
+    }
+
+    #[test]
+    fn truncate_ends_with_an_ellipsis_within_the_limit() {
+        let truncated = truncate("Some rather long summary", 12);
+        assert_eq!(truncated, "Some rath...");
+        assert_eq!(truncated.chars().count(), 12);
+    }
+
+    #[test]
+    fn parse_due_adds_days_and_weeks() {
+        let today = Local::now().date_naive();
+        assert_eq!(parse_due("+3d"), Ok(today + Days::new(3)));
+        assert_eq!(parse_due("+2w"), Ok(today + Days::new(14)));
+    }
+
+    #[test]
+    fn parse_due_rejects_overflowing_periods() {
+        assert!(parse_due(&format!("+{}w", u64::MAX / 6)).is_err());
+        assert!(parse_due(&format!("+{}d", u64::MAX)).is_err());
+    }
+
+    #[test]
+    fn jql_string_escapes_quotes_and_backslashes() {
+        assert_eq!(jql_string("In Progress"), r#""In Progress""#);
+        assert_eq!(
+            jql_string(r#"Done" OR project = "X"#),
+            r#""Done\" OR project = \"X""#
+        );
+        assert_eq!(jql_string(r"a\"), r#""a\\""#);
+    }
+
+    #[test]
+    fn create_temp_file_uses_a_fresh_path_each_time() {
+        let first = create_temp_file("fast-task-test", ".md", "text").unwrap();
+        let second = create_temp_file("fast-task-test", ".md", "text").unwrap();
+        assert_ne!(first, second);
+        assert_eq!(fs::read_to_string(&first).unwrap(), "text");
+        let _ = fs::remove_file(&first);
+        let _ = fs::remove_file(&second);
+    }
 }
@@ -0,0 +1,264 @@
+use reqwest::{Client, Url};
+use serde::Deserialize;
+use serde_json::json;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+use crate::config::OAuthTokens;
+
+const AUTHORIZE_URL: &str = "https://auth.atlassian.com/authorize";
+const TOKEN_URL: &str = "https://auth.atlassian.com/oauth/token";
+const RESOURCES_URL: &str = "https://api.atlassian.com/oauth/token/accessible-resources";
+/// `offline_access` is what makes Atlassian issue a refresh token
+const SCOPES: &str = "read:jira-work write:jira-work read:jira-user offline_access";
+/// How long `login` waits for the browser to come back
+const CALLBACK_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Error)]
+pub enum OAuthError {
+    #[error("OAuth request error: {0}")]
+    Request(String),
+    #[error("Authorization was denied: {0}")]
+    Denied(String),
+    #[error("The callback state doesn't match, try logging in again")]
+    StateMismatch,
+    #[error("Invalid callback: {0}")]
+    Callback(String),
+    #[error("No browser callback within {} minutes", CALLBACK_TIMEOUT.as_secs() / 60)]
+    Timeout,
+    #[error("Cannot generate the login state: {0}")]
+    Random(String),
+    #[error("Bad token response, status: {0}, text: {1}")]
+    Response(reqwest::StatusCode, String),
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_in: u64,
+}
+
+/// Jira site the tokens grant access to
+#[derive(Deserialize, Debug, Clone)]
+pub struct AccessibleResource {
+    pub id: String,
+    pub url: String,
+    pub name: String,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+/// Unguessable value tying the callback to this login attempt, 16 bytes from the
+/// operating system's secure random source
+pub fn random_state() -> Result<String, OAuthError> {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).map_err(|err| OAuthError::Random(err.to_string()))?;
+    Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+pub fn redirect_uri(port: u16) -> String {
+    format!("http://localhost:{}/callback", port)
+}
+
+pub fn authorize_url(client_id: &str, redirect_uri: &str, state: &str) -> Url {
+    Url::parse_with_params(
+        AUTHORIZE_URL,
+        &[
+            ("audience", "api.atlassian.com"),
+            ("client_id", client_id),
+            ("scope", SCOPES),
+            ("redirect_uri", redirect_uri),
+            ("state", state),
+            ("response_type", "code"),
+            ("prompt", "consent"),
+        ],
+    )
+    .expect("Authorize url must be valid")
+}
+
+/// Answers requests on the redirect uri until one carries the authorization code
+pub async fn wait_for_code(listener: TcpListener, state: &str) -> Result<String, OAuthError> {
+    tokio::time::timeout(CALLBACK_TIMEOUT, accept_callback(listener, state))
+        .await
+        .map_err(|_| OAuthError::Timeout)?
+}
+
+async fn accept_callback(listener: TcpListener, state: &str) -> Result<String, OAuthError> {
+    loop {
+        let (mut stream, _) = listener
+            .accept()
+            .await
+            .map_err(|err| OAuthError::Callback(err.to_string()))?;
+
+        let mut buffer = vec![0; 8192];
+        let read = stream
+            .read(&mut buffer)
+            .await
+            .map_err(|err| OAuthError::Callback(err.to_string()))?;
+        let request = String::from_utf8_lossy(&buffer[..read]);
+        let path = request
+            .lines()
+            .next()
+            .and_then(|line| line.split_whitespace().nth(1))
+            .unwrap_or("/");
+
+        // Browsers also ask for things like /favicon.ico
+        if !path.starts_with("/callback") {
+            let _ = stream
+                .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n")
+                .await;
+            continue;
+        }
+
+        let url = Url::parse(&format!("http://localhost{}", path))
+            .map_err(|err| OAuthError::Callback(err.to_string()))?;
+        let param = |name: &str| {
+            url.query_pairs()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.into_owned())
+        };
+        let result = if let Some(error) = param("error") {
+            Err(OAuthError::Denied(
+                param("error_description").unwrap_or(error),
+            ))
+        } else if param("state").as_deref() != Some(state) {
+            Err(OAuthError::StateMismatch)
+        } else {
+            param("code").ok_or_else(|| OAuthError::Callback("no code in the callback".to_string()))
+        };
+
+        let message = match result {
+            Ok(_) => "fast-task is logged in, you can close this window.",
+            Err(_) => "fast-task login failed, see the terminal for details.",
+        };
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            message.len(),
+            message
+        );
+        let _ = stream.write_all(response.as_bytes()).await;
+        return result;
+    }
+}
+
+async fn request_tokens(
+    http: &Client,
+    body: serde_json::Value,
+) -> Result<TokenResponse, OAuthError> {
+    let response = http
+        .post(TOKEN_URL)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|err| OAuthError::Request(err.to_string()))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let text = response.text().await.unwrap_or_default();
+        return Err(OAuthError::Response(status, text));
+    }
+    response
+        .json()
+        .await
+        .map_err(|err| OAuthError::Request(err.to_string()))
+}
+
+/// Trades the authorization code for tokens, `cloud_id` is filled in later
+pub async fn exchange_code(
+    http: &Client,
+    client_id: &str,
+    client_secret: &str,
+    code: &str,
+    redirect_uri: &str,
+) -> Result<OAuthTokens, OAuthError> {
+    let tokens = request_tokens(
+        http,
+        json!({
+            "grant_type": "authorization_code",
+            "client_id": client_id,
+            "client_secret": client_secret,
+            "code": code,
+            "redirect_uri": redirect_uri,
+        }),
+    )
+    .await?;
+
+    Ok(OAuthTokens {
+        client_id: client_id.to_string(),
+        client_secret: client_secret.to_string(),
+        access_token: tokens.access_token,
+        refresh_token: tokens.refresh_token.unwrap_or_default(),
+        expires_at: now_secs() + tokens.expires_in,
+        cloud_id: String::new(),
+    })
+}
+
+/// Atlassian rotates refresh tokens, the returned one replaces the old
+pub async fn refresh_tokens(
+    http: &Client,
+    tokens: &OAuthTokens,
+) -> Result<OAuthTokens, OAuthError> {
+    let refreshed = request_tokens(
+        http,
+        json!({
+            "grant_type": "refresh_token",
+            "client_id": tokens.client_id,
+            "client_secret": tokens.client_secret,
+            "refresh_token": tokens.refresh_token,
+        }),
+    )
+    .await?;
+
+    Ok(OAuthTokens {
+        access_token: refreshed.access_token,
+        refresh_token: refreshed
+            .refresh_token
+            .unwrap_or_else(|| tokens.refresh_token.clone()),
+        expires_at: now_secs() + refreshed.expires_in,
+        ..tokens.clone()
+    })
+}
+
+pub async fn accessible_resources(
+    http: &Client,
+    access_token: &str,
+) -> Result<Vec<AccessibleResource>, OAuthError> {
+    let response = http
+        .get(RESOURCES_URL)
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .map_err(|err| OAuthError::Request(err.to_string()))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let text = response.text().await.unwrap_or_default();
+        return Err(OAuthError::Response(status, text));
+    }
+    response
+        .json()
+        .await
+        .map_err(|err| OAuthError::Request(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random_state_is_32_hex_digits_and_differs_per_call() {
+        let first = random_state().unwrap();
+        let second = random_state().unwrap();
+        assert_eq!(first.len(), 32);
+        assert!(first.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(first, second);
+    }
+}
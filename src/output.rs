@@ -0,0 +1,115 @@
+use inquire::ui::RenderConfig;
+use std::env;
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static PLAIN: AtomicBool = AtomicBool::new(false);
+static QUIET: AtomicBool = AtomicBool::new(false);
+static TO_STDERR: AtomicBool = AtomicBool::new(false);
+/// Whether the last line was kept in quiet mode, indented lines follow it
+static LAST_KEPT: AtomicBool = AtomicBool::new(false);
+
+/// Like `println!`, but drops emoji under `--no-color` or `NO_COLOR`
+macro_rules! say {
+    () => {
+        $crate::output::print(String::new())
+    };
+    ($($arg:tt)*) => {
+        $crate::output::print(format!($($arg)*))
+    };
+}
+
+/// `eprintln!` counterpart of `say!`
+macro_rules! esay {
+    ($($arg:tt)*) => {
+        eprintln!("{}", $crate::output::decorate(format!($($arg)*)))
+    };
+}
+
+/// Any non-empty `NO_COLOR` value counts, see https://no-color.org
+pub fn no_color_requested(flag: bool) -> bool {
+    flag || env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+/// Switches all output to plain text, must run before the first prompt is shown
+pub fn init(plain: bool) {
+    PLAIN.store(plain, Ordering::Relaxed);
+    if plain {
+        inquire::set_global_render_config(RenderConfig::empty());
+    }
+}
+
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+/// Keeps stdout for the result alone: progress is dropped, errors, warnings and
+/// hints go to stderr
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Sends every `say!` line to stderr, stdout is left for the `--json` result
+pub fn set_stderr(to_stderr: bool) {
+    TO_STDERR.store(to_stderr, Ordering::Relaxed);
+}
+
+/// Backs `say!`, in quiet mode a line is kept when it starts with ❌, ⚠️ or 💡,
+/// or is indented below a kept one
+pub fn print(text: String) {
+    if !QUIET.load(Ordering::Relaxed) {
+        if TO_STDERR.load(Ordering::Relaxed) {
+            eprintln!("{}", decorate(text));
+        } else {
+            println!("{}", decorate(text));
+        }
+        return;
+    }
+    let keep = if text.starts_with(' ') {
+        LAST_KEPT.load(Ordering::Relaxed)
+    } else {
+        text.trim_start_matches('\n').starts_with(['❌', '⚠', '💡'])
+    };
+    LAST_KEPT.store(keep, Ordering::Relaxed);
+    if keep {
+        eprintln!("{}", decorate(text));
+    }
+}
+
+/// Colors a status name by its category: to do gray, in progress blue, done green.
+/// Left as is in plain mode and when stdout isn't a terminal
+pub fn status(text: &str, category: Option<&str>) -> String {
+    let color = match category {
+        Some("new") => "90",
+        Some("indeterminate") => "34",
+        Some("done") => "32",
+        _ => return text.to_string(),
+    };
+    if is_plain() || !io::stdout().is_terminal() {
+        return text.to_string();
+    }
+    format!("\x1b[{}m{}\x1b[0m", color, text)
+}
+
+fn is_decoration(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1F000..=0x1FAFF | 0x2600..=0x27BF | 0x2B00..=0x2BFF | 0x2139 | 0xFE0F | 0x200D
+    )
+}
+
+/// Strips the emoji a message starts with, along with the space after it.
+/// Only the leading one is decoration, text from Jira may contain emoji too
+pub fn decorate(text: String) -> String {
+    if !is_plain() {
+        return text;
+    }
+    let indent = text.len() - text.trim_start().len();
+    let (leading, rest) = text.split_at(indent);
+    let Some(message) = rest.strip_prefix(is_decoration) else {
+        return text;
+    };
+    let message = message.trim_start_matches(is_decoration);
+    let message = message.strip_prefix(' ').unwrap_or(message);
+    format!("{}{}", leading, message)
+}
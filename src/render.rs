@@ -0,0 +1,100 @@
+use clap::ValueEnum;
+
+use crate::jira_client::IssueSummary;
+use crate::output;
+
+/// How listings are printed, picked with `--output`
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Aligned columns for reading in the terminal
+    #[default]
+    Table,
+    /// Comma-separated values with a header row, for spreadsheets
+    Csv,
+    /// Array of issue objects
+    Json,
+}
+
+/// Prints the issues in the requested format. CSV and JSON skip `say!`, the leading
+/// emoji it strips in plain mode may belong to a summary
+pub fn print_issues(issues: &[IssueSummary], format: OutputFormat) {
+    match format {
+        OutputFormat::Table => print_table(issues),
+        OutputFormat::Csv => {
+            println!("{}", csv_row(["key", "status", "assignee", "summary"]));
+            for issue in issues {
+                println!(
+                    "{}",
+                    csv_row([
+                        issue.key.as_str(),
+                        issue.status.as_str(),
+                        issue.assignee.as_deref().unwrap_or_default(),
+                        issue.summary.as_str(),
+                    ])
+                );
+            }
+        }
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(issues).unwrap_or_else(|_| "[]".to_string())
+        ),
+    }
+}
+
+/// Joins the fields into one CSV line
+pub fn csv_row<'a>(fields: impl IntoIterator<Item = &'a str>) -> String {
+    fields
+        .into_iter()
+        .map(csv_field)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Quotes a field when it holds a separator, a quote or a line break, doubling the
+/// quotes inside as RFC 4180 wants
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn print_table(issues: &[IssueSummary]) {
+    let key_width = issues
+        .iter()
+        .map(|issue| issue.key.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("KEY".len());
+    let status_width = issues
+        .iter()
+        .map(|issue| issue.status.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("STATUS".len());
+    let assignee_width = issues
+        .iter()
+        .map(|issue| issue.assignee.as_deref().unwrap_or("-").chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("ASSIGNEE".len());
+
+    say!(
+        "{:key_width$}  {:status_width$}  {:assignee_width$}  SUMMARY",
+        "KEY",
+        "STATUS",
+        "ASSIGNEE"
+    );
+    for issue in issues {
+        // Padded before coloring, the escape codes would count towards the width
+        let status = format!("{:status_width$}", issue.status);
+        say!(
+            "{:key_width$}  {}  {:assignee_width$}  {}",
+            issue.key,
+            output::status(&status, issue.status_category.as_deref()),
+            issue.assignee.as_deref().unwrap_or("-"),
+            issue.summary
+        );
+    }
+}